@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest};
+use crate::rooms::normalize_shard_for;
+
+const DEFAULT_PVP_INTERVAL: u64 = 100;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsPvpFeedRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub interval: Option<u64>,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsNukesRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PvpRoomEntry {
+    pub room: String,
+    pub shard: String,
+    pub last_pvp_time: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NukeEntry {
+    pub room: String,
+    pub shard: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_room: Option<String>,
+    pub land_time: u64,
+}
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn value_as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_f64().filter(|raw| *raw >= 0.0).map(|raw| raw as u64),
+        Value::String(text) => text.trim().parse::<u64>().ok(),
+        _ => None,
+    }
+}
+
+// The experimental endpoints only exist on the official server; anything else yields no data.
+async fn experimental_get(
+    base_url: String,
+    token: String,
+    username: String,
+    endpoint: &str,
+    query: HashMap<String, Value>,
+) -> Result<Option<Value>, String> {
+    if token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url,
+            endpoint: endpoint.to_string(),
+            method: Some("GET".to_string()),
+            token: Some(token),
+            username: Some(username),
+            query: Some(query),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if response.status == 404 || response.data.get("text").is_some() {
+        return Ok(None);
+    }
+    if !response.ok {
+        return Err(format!("{} request failed: HTTP {}", endpoint, response.status));
+    }
+    if let Some(error) = response.data.get("error").and_then(value_as_non_empty_string) {
+        return Err(error);
+    }
+    Ok(Some(response.data))
+}
+
+fn shard_entries<'a>(
+    payload: &'a Value,
+    key: &str,
+    shard: Option<&'a str>,
+) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    payload
+        .get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter(move |(name, _)| shard.is_none_or(|value| value.eq_ignore_ascii_case(name)))
+}
+
+#[tauri::command]
+pub async fn screeps_pvp_feed(request: ScreepsPvpFeedRequest) -> Result<Vec<PvpRoomEntry>, String> {
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let interval = request.interval.unwrap_or(DEFAULT_PVP_INTERVAL).max(1);
+    let query = HashMap::from([("interval".to_string(), json!(interval))]);
+    let Some(payload) = experimental_get(
+        request.base_url,
+        request.token,
+        request.username,
+        "/api/experimental/pvp",
+        query,
+    )
+    .await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = shard_entries(&payload, "pvp", shard.as_deref())
+        .flat_map(|(shard_name, value)| {
+            value.get("rooms").and_then(Value::as_array).into_iter().flatten().filter_map(
+                move |room| {
+                    Some(PvpRoomEntry {
+                        room: room.get("_id").and_then(value_as_non_empty_string)?,
+                        shard: shard_name.clone(),
+                        last_pvp_time: room.get("lastPvpTime").and_then(value_as_u64)?,
+                    })
+                },
+            )
+        })
+        .collect::<Vec<PvpRoomEntry>>();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_pvp_time));
+    Ok(entries)
+}
+
+pub(crate) async fn fetch_nukes(request: ScreepsNukesRequest) -> Result<Vec<NukeEntry>, String> {
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let Some(payload) = experimental_get(
+        request.base_url,
+        request.token,
+        request.username,
+        "/api/experimental/nukes",
+        HashMap::new(),
+    )
+    .await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = shard_entries(&payload, "nukes", shard.as_deref())
+        .flat_map(|(shard_name, value)| {
+            value.as_array().into_iter().flatten().filter_map(move |nuke| {
+                Some(NukeEntry {
+                    room: nuke.get("room").and_then(value_as_non_empty_string)?,
+                    shard: shard_name.clone(),
+                    launch_room: nuke.get("launchRoomName").and_then(value_as_non_empty_string),
+                    land_time: nuke.get("landTime").and_then(value_as_u64)?,
+                })
+            })
+        })
+        .collect::<Vec<NukeEntry>>();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.land_time));
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn screeps_nukes(request: ScreepsNukesRequest) -> Result<Vec<NukeEntry>, String> {
+    fetch_nukes(request).await
+}
@@ -0,0 +1,448 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest, ScreepsResponse};
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsAuthSigninRequest {
+    pub base_url: String,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsAuthSignin2faRequest {
+    pub base_url: String,
+    pub email: String,
+    pub password: String,
+    pub challenge_id: Option<String>,
+    pub code: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsAuthSigninResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub needs_2fa: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsAuthCheckRequest {
+    pub base_url: String,
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsAuthCheckResponse {
+    pub ok: bool,
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gcl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AuthMeResponse {
+    ok: i64,
+    #[serde(rename = "_id")]
+    pub(crate) self_id: String,
+    pub(crate) username: String,
+    #[serde(default)]
+    pub(crate) gcl: Option<f64>,
+    #[serde(default)]
+    pub(crate) badge: Option<Value>,
+    #[serde(default)]
+    pub(crate) power: Option<f64>,
+    #[serde(default)]
+    pub(crate) money: Option<f64>,
+    #[serde(default)]
+    pub(crate) cpu: Option<f64>,
+    #[serde(default, rename = "cpuShard")]
+    pub(crate) cpu_shard: Option<HashMap<String, f64>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMeStatsRequest {
+    pub base_url: String,
+    pub token: String,
+    pub include_stats: Option<bool>,
+    pub stats_interval: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMeStatsResponse {
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gcl: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gcl_progress: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gcl_progress_total: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credits: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<HashMap<String, f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<HashMap<String, f64>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsWorldStatusResponse {
+    pub status: String,
+}
+
+const GCL_MULTIPLY: f64 = 1_000_000.0;
+const GCL_POW: f64 = 2.4;
+const POWER_LEVEL_MULTIPLY: f64 = 1_000.0;
+const POWER_LEVEL_POW: f64 = 2.0;
+// `/api/auth/me` reports money in thousandths of a credit.
+const MONEY_PER_CREDIT: f64 = 1_000.0;
+const USER_STATS_INTERVALS: [u32; 3] = [8, 180, 1440];
+
+fn gcl_progress(points: f64) -> (u32, f64, f64) {
+    let level = (points.max(0.0) / GCL_MULTIPLY).powf(1.0 / GCL_POW).floor() + 1.0;
+    let floor = GCL_MULTIPLY * (level - 1.0).powf(GCL_POW);
+    let ceiling = GCL_MULTIPLY * level.powf(GCL_POW);
+    (level as u32, points - floor, ceiling - floor)
+}
+
+fn power_level(points: f64) -> u32 {
+    (points.max(0.0) / POWER_LEVEL_MULTIPLY).powf(1.0 / POWER_LEVEL_POW).floor() as u32
+}
+
+fn server_error_message(payload: &Value) -> Option<String> {
+    ["error", "message", "text"]
+        .iter()
+        .filter_map(|key| payload.get(*key).and_then(Value::as_str))
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+// A 2FA account answers the password step with `{ "twoFactor": true, "challengeId"? }` and no
+// token; the code is then posted together with the same credentials.
+fn two_factor_challenge(payload: &Value) -> Option<Option<String>> {
+    if payload.get("twoFactor").and_then(Value::as_bool) != Some(true) {
+        return None;
+    }
+    let challenge_id = payload
+        .get("challengeId")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    Some(challenge_id)
+}
+
+fn signin_body(email: &str, password: &str, two_factor: Option<(&str, Option<&str>)>) -> Value {
+    let mut body = json!({ "email": email, "password": password });
+    if let Some((code, challenge_id)) = two_factor {
+        body["twoFactorToken"] = json!(code);
+        if let Some(challenge_id) = challenge_id {
+            body["challengeId"] = json!(challenge_id);
+        }
+    }
+    body
+}
+
+fn signin_token(response: ScreepsResponse) -> Result<String, String> {
+    let rejected = response.data.get("ok").and_then(Value::as_i64) == Some(0);
+    if !response.ok || rejected {
+        return Err(server_error_message(&response.data)
+            .unwrap_or_else(|| format!("sign-in request failed: HTTP {}", response.status)));
+    }
+
+    response
+        .data
+        .get("token")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or(response.token)
+        .ok_or_else(|| "sign-in response did not include a token".to_string())
+}
+
+fn normalize_token(token: &str) -> Result<String, String> {
+    let trimmed = token.trim();
+    if trimmed.is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    if trimmed.chars().any(char::is_whitespace) {
+        return Err("Token must not contain whitespace".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+pub(crate) async fn fetch_auth_profile(
+    base_url: &str,
+    token: &str,
+) -> Result<AuthMeResponse, String> {
+    let token = normalize_token(token)?;
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: base_url.to_string(),
+            endpoint: "/api/auth/me".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(token),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if response.status == 401 {
+        return Err("auth profile request rejected: invalid or expired token".to_string());
+    }
+    if !response.ok {
+        return Err(format!("auth profile request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = server_error_message(&response.data) {
+        return Err(error);
+    }
+
+    let payload = serde_json::from_value::<AuthMeResponse>(response.data)
+        .map_err(|error| format!("failed to parse /api/auth/me payload: {}", error))?;
+    if payload.ok != 1 {
+        return Err("auth profile returned ok!=1".to_string());
+    }
+    Ok(payload)
+}
+
+#[tauri::command]
+pub async fn screeps_auth_check(
+    request: ScreepsAuthCheckRequest,
+) -> Result<ScreepsAuthCheckResponse, String> {
+    let profile = fetch_auth_profile(&request.base_url, &request.token).await?;
+    Ok(ScreepsAuthCheckResponse {
+        ok: true,
+        username: profile.username,
+        gcl: profile.gcl,
+        badge: profile.badge,
+    })
+}
+
+#[tauri::command]
+pub async fn screeps_me_stats(
+    request: ScreepsMeStatsRequest,
+) -> Result<ScreepsMeStatsResponse, String> {
+    let profile = fetch_auth_profile(&request.base_url, &request.token).await?;
+    let gcl = profile.gcl.map(gcl_progress);
+    let stats = if request.include_stats.unwrap_or(false) {
+        let interval = request.stats_interval.unwrap_or(USER_STATS_INTERVALS[0]);
+        if !USER_STATS_INTERVALS.contains(&interval) {
+            return Err(format!("Stats interval must be one of {:?}", USER_STATS_INTERVALS));
+        }
+        // Private servers often lack this endpoint; the profile fields are still useful alone.
+        fetch_user_stats(&request.base_url, &request.token, interval).await.ok()
+    } else {
+        None
+    };
+    Ok(ScreepsMeStatsResponse {
+        username: profile.username,
+        gcl: gcl.map(|(level, _, _)| level),
+        gcl_progress: gcl.map(|(_, progress, _)| progress),
+        gcl_progress_total: gcl.map(|(_, _, total)| total),
+        power: profile.power.map(power_level),
+        credits: profile.money.map(|money| money / MONEY_PER_CREDIT),
+        cpu: profile.cpu_shard,
+        cpu_limit: profile.cpu,
+        stats,
+    })
+}
+
+async fn fetch_user_stats(
+    base_url: &str,
+    token: &str,
+    interval: u32,
+) -> Result<HashMap<String, f64>, String> {
+    let token = normalize_token(token)?;
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: base_url.to_string(),
+            endpoint: "/api/user/stats".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(token),
+            query: Some(HashMap::from([("interval".to_string(), json!(interval))])),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("user stats request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = server_error_message(&response.data) {
+        return Err(error);
+    }
+    let stats = response
+        .data
+        .get("stats")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "user stats response did not include stats".to_string())?;
+    Ok(stats.iter().filter_map(|(key, value)| Some((key.clone(), value.as_f64()?))).collect())
+}
+
+#[tauri::command]
+pub async fn screeps_world_status(
+    request: ScreepsAuthCheckRequest,
+) -> Result<ScreepsWorldStatusResponse, String> {
+    let token = normalize_token(&request.token)?;
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url,
+            endpoint: "/api/user/world-status".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(token),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("world status request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = response.data.get("error").and_then(Value::as_str) {
+        return Err(error.trim().to_string());
+    }
+
+    // "normal", "lost" and "empty" are the known values; anything else is passed through as-is.
+    let status = response
+        .data
+        .get("status")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_ascii_lowercase)
+        .ok_or_else(|| "world status response did not include a status".to_string())?;
+    Ok(ScreepsWorldStatusResponse { status })
+}
+
+#[tauri::command]
+pub async fn screeps_auth_signin(
+    request: ScreepsAuthSigninRequest,
+) -> Result<ScreepsAuthSigninResponse, String> {
+    let email = request.email.trim();
+    if email.is_empty() {
+        return Err("Email cannot be empty".to_string());
+    }
+    if request.password.is_empty() {
+        return Err("Password cannot be empty".to_string());
+    }
+
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url,
+            endpoint: "/api/auth/signin".to_string(),
+            method: Some("POST".to_string()),
+            body: Some(signin_body(email, &request.password, None)),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if let Some(challenge_id) = two_factor_challenge(&response.data) {
+        return Ok(ScreepsAuthSigninResponse {
+            needs_2fa: true,
+            challenge_id,
+            ..Default::default()
+        });
+    }
+
+    let token = signin_token(response)?;
+    Ok(ScreepsAuthSigninResponse { token: Some(token), ..Default::default() })
+}
+
+#[tauri::command]
+pub async fn screeps_auth_signin_2fa(
+    request: ScreepsAuthSignin2faRequest,
+) -> Result<ScreepsAuthSigninResponse, String> {
+    let email = request.email.trim();
+    if email.is_empty() {
+        return Err("Email cannot be empty".to_string());
+    }
+    if request.password.is_empty() {
+        return Err("Password cannot be empty".to_string());
+    }
+    let code = request.code.trim();
+    if code.is_empty() {
+        return Err("Verification code cannot be empty".to_string());
+    }
+    let challenge_id =
+        request.challenge_id.as_deref().map(str::trim).filter(|value| !value.is_empty());
+
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url,
+            endpoint: "/api/auth/signin".to_string(),
+            method: Some("POST".to_string()),
+            body: Some(signin_body(email, &request.password, Some((code, challenge_id)))),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let token = signin_token(response)?;
+    Ok(ScreepsAuthSigninResponse { token: Some(token), ..Default::default() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_two_factor_challenge_payload() {
+        let payload = json!({ "ok": 0, "twoFactor": true, "challengeId": " 64f0c2a1 " });
+        assert_eq!(two_factor_challenge(&payload), Some(Some("64f0c2a1".to_string())));
+
+        let without_id = json!({ "ok": 0, "twoFactor": true });
+        assert_eq!(two_factor_challenge(&without_id), Some(None));
+
+        let signed_in = json!({ "ok": 1, "token": "abc" });
+        assert_eq!(two_factor_challenge(&signed_in), None);
+        let rejected = json!({ "error": "invalid credentials" });
+        assert_eq!(two_factor_challenge(&rejected), None);
+    }
+
+    #[test]
+    fn two_factor_resubmission_resends_credentials() {
+        let body = signin_body("user@example.com", "hunter2", Some(("123456", Some("64f0c2a1"))));
+        assert_eq!(
+            body,
+            json!({
+                "email": "user@example.com",
+                "password": "hunter2",
+                "twoFactorToken": "123456",
+                "challengeId": "64f0c2a1",
+            })
+        );
+        let plain = signin_body("user@example.com", "hunter2", None);
+        assert_eq!(plain, json!({ "email": "user@example.com", "password": "hunter2" }));
+    }
+}
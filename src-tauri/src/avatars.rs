@@ -0,0 +1,177 @@
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
+use tokio::sync::Semaphore;
+
+use crate::http::{fetch_binary, shared_http_client};
+
+static AVATAR_CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static AVATAR_DOWNLOADS: OnceLock<Semaphore> = OnceLock::new();
+static AVATAR_LOCKS: OnceLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    OnceLock::new();
+
+const MAX_CONCURRENT_AVATAR_DOWNLOADS: usize = 4;
+const MAX_AVATAR_BYTES: usize = 2 * 1024 * 1024;
+const AVATAR_CACHE_MAX_BYTES: u64 = 16 * 1024 * 1024;
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsAvatarFetchRequest {
+    pub base_url: String,
+    pub token: Option<String>,
+    pub username: Option<String>,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsAvatarFetchResponse {
+    pub data_uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+pub(crate) fn set_avatar_cache_dir(dir: PathBuf) {
+    let _ = AVATAR_CACHE_DIR.set(dir);
+}
+
+fn avatar_downloads() -> &'static Semaphore {
+    AVATAR_DOWNLOADS.get_or_init(|| Semaphore::new(MAX_CONCURRENT_AVATAR_DOWNLOADS))
+}
+
+fn avatar_lock(url: &str) -> Arc<tokio::sync::Mutex<()>> {
+    let Ok(mut guard) = AVATAR_LOCKS.get_or_init(|| Mutex::new(HashMap::new())).lock() else {
+        return Arc::new(tokio::sync::Mutex::new(()));
+    };
+    // Drop locks nobody else is holding so the map does not grow with every avatar seen.
+    guard.retain(|_, lock| Arc::strong_count(lock) > 1);
+    guard.entry(url.to_string()).or_default().clone()
+}
+
+// FNV-1a is fixed by definition, unlike `DefaultHasher`, so file names survive toolchain upgrades.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME))
+}
+
+fn avatar_path(url: &str) -> Option<PathBuf> {
+    Some(AVATAR_CACHE_DIR.get()?.join(format!("{:016x}.img", fnv1a_64(url.as_bytes()))))
+}
+
+// Oldest avatars go first once the directory outgrows its cap.
+fn evict_oversized(dir: &Path) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files = read_dir
+        .filter_map(Result::ok)
+        .filter(|item| item.path().extension().is_some_and(|extension| extension == "img"))
+        .filter_map(|item| {
+            let metadata = item.metadata().ok().filter(|metadata| metadata.is_file())?;
+            Some((item.path(), metadata.len(), metadata.modified().unwrap_or(UNIX_EPOCH)))
+        })
+        .collect::<Vec<_>>();
+    let mut total = files.iter().map(|(_, len, _)| len).sum::<u64>();
+    if total <= AVATAR_CACHE_MAX_BYTES {
+        return;
+    }
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (path, len, _) in files {
+        if total <= AVATAR_CACHE_MAX_BYTES {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(len);
+        }
+    }
+}
+
+fn sniff_content_type(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if bytes.starts_with(b"\xFF\xD8\xFF") {
+        "image/jpeg"
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif"
+    } else if bytes.len() > 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else if bytes.trim_ascii_start().starts_with(b"<") {
+        "image/svg+xml"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+fn avatar_response(bytes: &[u8], path: Option<PathBuf>) -> ScreepsAvatarFetchResponse {
+    ScreepsAvatarFetchResponse {
+        data_uri: format!(
+            "data:{};base64,{}",
+            sniff_content_type(bytes),
+            BASE64_STANDARD.encode(bytes)
+        ),
+        path: path.map(|path| path.to_string_lossy().to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn screeps_avatar_fetch(
+    request: ScreepsAvatarFetchRequest,
+) -> Result<ScreepsAvatarFetchResponse, String> {
+    let url = request.url.trim();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Invalid avatar URL: {}", request.url));
+    }
+
+    let lock = avatar_lock(url);
+    let _url_guard = lock.lock().await;
+    let path = avatar_path(url);
+    if let Some(bytes) = path.as_ref().and_then(|path| fs::read(path).ok()) {
+        return Ok(avatar_response(&bytes, path));
+    }
+
+    let _permit = avatar_downloads()
+        .acquire()
+        .await
+        .map_err(|error| format!("avatar download queue closed: {}", error))?;
+    let client = shared_http_client()?;
+    let bytes = fetch_binary(
+        &client,
+        &request.base_url,
+        url,
+        request.token.as_deref(),
+        request.username.as_deref(),
+    )
+    .await?;
+    if bytes.len() > MAX_AVATAR_BYTES {
+        return Err(format!("Avatar is too large: {} bytes", bytes.len()));
+    }
+
+    let stored = path.filter(|path| {
+        path.parent().is_some_and(|parent| fs::create_dir_all(parent).is_ok())
+            && fs::write(path, &bytes).is_ok()
+    });
+    if let Some(dir) = stored.as_deref().and_then(Path::parent) {
+        evict_oversized(dir);
+    }
+    Ok(avatar_response(&bytes, stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn avatar_file_names_use_stable_fnv1a() {
+        // Reference FNV-1a 64-bit vectors; a change here would orphan every cached avatar.
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use crate::http::{
+    clear_response_cache, decode_gz_text, perform_screeps_request, shared_http_client,
+    ScreepsRequest,
+};
+use crate::rooms::normalize_shard_for;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsCodeFetchRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub branch: Option<String>,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsCodeBranchesRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsCodePushRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub branch: String,
+    pub modules: HashMap<String, Value>,
+    pub set_active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsCodePushResponse {
+    pub ok: bool,
+    pub branch: String,
+    pub modules: usize,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub activated: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsCodeFetchResponse {
+    pub branch: String,
+    pub modules: HashMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeBranchSummary {
+    pub branch: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub active_world: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub active_sim: bool,
+}
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn payload_error(payload: &Value) -> Option<String> {
+    payload.get("error").and_then(value_as_non_empty_string)
+}
+
+// `$activeWorld` is the server's alias for whichever branch currently runs on the live shards.
+fn normalize_branch(branch: Option<&str>) -> String {
+    branch.map(str::trim).filter(|value| !value.is_empty()).unwrap_or("$activeWorld").to_string()
+}
+
+fn validate_modules(modules: &HashMap<String, Value>) -> Result<(), String> {
+    if modules.is_empty() {
+        return Err("Modules cannot be empty".to_string());
+    }
+    for (name, source) in modules {
+        if name.trim().is_empty() {
+            return Err("Module names cannot be empty".to_string());
+        }
+        // Binary (wasm) modules are uploaded as `{ "binary": "<base64>" }`.
+        let valid = match source {
+            Value::String(_) => true,
+            Value::Object(map) => map.get("binary").is_some_and(Value::is_string),
+            _ => false,
+        };
+        if !valid {
+            return Err(format!("Module {} must be source text or a binary object", name));
+        }
+    }
+    Ok(())
+}
+
+fn decode_modules(modules: Value) -> Result<Map<String, Value>, String> {
+    match modules {
+        Value::Object(map) => Ok(map),
+        Value::String(text) if text.trim_start().starts_with("gz:") => {
+            let decoded = decode_gz_text(&text)
+                .ok_or_else(|| "failed to decode compressed code payload".to_string())?;
+            match serde_json::from_str::<Value>(&decoded) {
+                Ok(Value::Object(map)) => Ok(map),
+                Ok(_) => Err("code payload modules were not an object".to_string()),
+                Err(error) => Err(format!("failed to parse code payload: {}", error)),
+            }
+        }
+        Value::Null => Ok(Map::new()),
+        _ => Err("code response modules were not an object".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn screeps_code_fetch(
+    request: ScreepsCodeFetchRequest,
+) -> Result<ScreepsCodeFetchResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let branch = normalize_branch(request.branch.as_deref());
+    let mut query = HashMap::from([("branch".to_string(), Value::String(branch.clone()))]);
+    if let Some(shard) = normalize_shard_for(&request.base_url, request.shard.as_deref()) {
+        query.insert("shard".to_string(), Value::String(shard));
+    }
+
+    let client = shared_http_client()?;
+    let mut response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url,
+            endpoint: "/api/user/code".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(request.token),
+            username: Some(request.username),
+            query: Some(query),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("code request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+
+    let resolved_branch =
+        response.data.get("branch").and_then(value_as_non_empty_string).unwrap_or(branch);
+    let modules = response.data.get_mut("modules").map(Value::take).unwrap_or(Value::Null);
+    Ok(ScreepsCodeFetchResponse {
+        branch: resolved_branch,
+        modules: decode_modules(modules)?.into_iter().collect(),
+    })
+}
+
+#[tauri::command]
+pub async fn screeps_code_branches(
+    request: ScreepsCodeBranchesRequest,
+) -> Result<Vec<CodeBranchSummary>, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url,
+            endpoint: "/api/user/branches".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(request.token),
+            username: Some(request.username),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("branches request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+
+    let Some(list) = response.data.get("list").and_then(Value::as_array) else {
+        return Err("branches response did not include a list".to_string());
+    };
+    let mut branches = list
+        .iter()
+        .filter_map(|item| {
+            Some(CodeBranchSummary {
+                branch: item.get("branch").and_then(value_as_non_empty_string)?,
+                active_world: item.get("activeWorld").and_then(Value::as_bool).unwrap_or(false),
+                active_sim: item.get("activeSim").and_then(Value::as_bool).unwrap_or(false),
+            })
+        })
+        .collect::<Vec<CodeBranchSummary>>();
+    branches.sort_by(|left, right| left.branch.cmp(&right.branch));
+    Ok(branches)
+}
+
+#[tauri::command]
+pub async fn screeps_code_push(
+    request: ScreepsCodePushRequest,
+) -> Result<ScreepsCodePushResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let branch = request.branch.trim().to_string();
+    if branch.is_empty() {
+        return Err("Branch cannot be empty".to_string());
+    }
+    validate_modules(&request.modules)?;
+    let module_count = request.modules.len();
+
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url.clone(),
+            endpoint: "/api/user/code".to_string(),
+            method: Some("POST".to_string()),
+            token: Some(request.token.clone()),
+            username: Some(request.username.clone()),
+            body: Some(json!({ "branch": branch, "modules": request.modules })),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("code upload failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+    clear_response_cache(Some(&request.base_url), Some("/api/user/code"));
+
+    let mut activated = false;
+    if request.set_active.unwrap_or(false) {
+        let response = perform_screeps_request(
+            &client,
+            ScreepsRequest {
+                base_url: request.base_url.clone(),
+                endpoint: "/api/user/set-active-branch".to_string(),
+                method: Some("POST".to_string()),
+                token: Some(request.token),
+                username: Some(request.username),
+                body: Some(json!({ "branch": branch, "activeName": "activeWorld" })),
+                ..Default::default()
+            },
+        )
+        .await?;
+        if !response.ok {
+            return Err(format!(
+                "code uploaded but branch activation failed: HTTP {}",
+                response.status
+            ));
+        }
+        if let Some(error) = payload_error(&response.data) {
+            return Err(format!("code uploaded but branch activation failed: {}", error));
+        }
+        clear_response_cache(Some(&request.base_url), Some("/api/user/branches"));
+        activated = true;
+    }
+
+    Ok(ScreepsCodePushResponse {
+        ok: response.data.get("ok").and_then(Value::as_i64) == Some(1),
+        branch,
+        modules: module_count,
+        activated,
+    })
+}
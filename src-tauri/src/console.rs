@@ -1,8 +1,21 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest};
+use crate::http::{
+    normalize_base_url, perform_screeps_request, shared_http_client, ScreepsRequest,
+};
+use crate::rooms::normalize_shard_for;
+use crate::shards::{is_single_shard_server, shard_names_for};
+
+const CONSOLE_HISTORY_LIMIT: usize = 100;
+
+static CONSOLE_VARIANT_CACHE: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+static CONSOLE_HISTORY: OnceLock<Mutex<HashMap<String, VecDeque<ConsoleHistoryEntry>>>> =
+    OnceLock::new();
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +25,36 @@ pub struct ScreepsConsoleExecuteRequest {
     username: String,
     code: String,
     shard: Option<String>,
+    preserve_ansi: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsConsoleBatchRequest {
+    base_url: String,
+    token: String,
+    username: String,
+    commands: Vec<String>,
+    shard: Option<String>,
+    #[serde(default)]
+    stop_on_error: bool,
+    preserve_ansi: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsConsoleHistoryRequest {
+    base_url: String,
+    username: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsoleHistoryEntry {
+    code: String,
+    timestamp: u64,
+    ok: bool,
+    used_variant: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -19,21 +62,61 @@ pub struct ScreepsConsoleExecuteRequest {
 pub struct ScreepsConsoleExecuteResponse {
     ok: bool,
     feedback: Option<String>,
+    log_lines: Vec<String>,
+    error_lines: Vec<String>,
     error: Option<String>,
     used_variant: Option<String>,
     tried_variants: Vec<String>,
 }
 
-fn normalize_console_shard(shard_input: Option<&str>) -> Option<String> {
-    let shard = shard_input?.trim().to_lowercase();
-    if !shard.starts_with("shard") {
-        return None;
-    }
-    let number_part = &shard[5..];
-    if number_part.is_empty() || !number_part.chars().all(|ch| ch.is_ascii_digit()) {
-        return None;
+fn console_history() -> &'static Mutex<HashMap<String, VecDeque<ConsoleHistoryEntry>>> {
+    CONSOLE_HISTORY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn console_variant_cache() -> &'static Mutex<HashMap<String, String>> {
+    CONSOLE_VARIANT_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn remembered_console_variant(base_url: &str) -> Option<String> {
+    console_variant_cache().lock().ok()?.get(&normalize_base_url(base_url)).cloned()
+}
+
+fn remember_console_variant(base_url: &str, variant: Option<&str>) {
+    let Ok(mut guard) = console_variant_cache().lock() else {
+        return;
+    };
+    match variant {
+        Some(variant) => guard.insert(normalize_base_url(base_url), variant.to_string()),
+        None => guard.remove(&normalize_base_url(base_url)),
+    };
+}
+
+fn console_history_key(base_url: &str, username: &str) -> String {
+    format!("{}|{}", normalize_base_url(base_url), username.trim().to_ascii_lowercase())
+}
+
+fn record_console_history(
+    request: &ScreepsConsoleExecuteRequest,
+    response: &ScreepsConsoleExecuteResponse,
+) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+    let Ok(mut guard) = console_history().lock() else {
+        return;
+    };
+    let entries =
+        guard.entry(console_history_key(&request.base_url, &request.username)).or_default();
+    entries.push_back(ConsoleHistoryEntry {
+        code: request.code.trim().to_string(),
+        timestamp,
+        ok: response.ok,
+        used_variant: response.used_variant.clone(),
+    });
+    while entries.len() > CONSOLE_HISTORY_LIMIT {
+        entries.pop_front();
     }
-    Some(shard)
 }
 
 fn value_as_non_empty_string(value: &Value) -> Option<String> {
@@ -67,8 +150,32 @@ fn is_opaque_token(value: &str) -> bool {
     hex_count >= 16
 }
 
-fn sanitize_console_feedback(value: Option<String>) -> Option<String> {
+fn strip_ansi_codes(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' && ch != '\u{9b}' {
+            output.push(ch);
+            continue;
+        }
+        // CSI sequences (`ESC [` or the single-byte form) run until a final byte in @..~;
+        // any other escape consumes just the following character.
+        if ch == '\u{9b}' || chars.next_if_eq(&'[').is_some() {
+            for next in chars.by_ref() {
+                if ('@'..='~').contains(&next) {
+                    break;
+                }
+            }
+        } else {
+            chars.next();
+        }
+    }
+    output
+}
+
+fn sanitize_console_feedback(value: Option<String>, strip_ansi: bool) -> Option<String> {
     let text = value?;
+    let text = if strip_ansi { strip_ansi_codes(&text) } else { text };
     let trimmed = text.trim();
     if trimmed.is_empty() {
         return None;
@@ -152,25 +259,33 @@ fn extract_payload_error(payload: &Value) -> Option<String> {
     None
 }
 
-fn extract_console_feedback_from_value(payload: &Value, depth: usize) -> Option<String> {
+fn extract_console_feedback_from_value(
+    payload: &Value,
+    depth: usize,
+    strip_ansi: bool,
+) -> Option<String> {
     if depth > 6 {
         return None;
     }
 
     match payload {
-        Value::String(_) => sanitize_console_feedback(value_as_non_empty_string(payload)),
+        Value::String(_) => {
+            sanitize_console_feedback(value_as_non_empty_string(payload), strip_ansi)
+        }
         Value::Array(items) => {
             let joined = items
                 .iter()
                 .filter_map(value_as_non_empty_string)
                 .collect::<Vec<String>>()
                 .join("\n");
-            let joined_feedback = sanitize_console_feedback(Some(joined));
+            let joined_feedback = sanitize_console_feedback(Some(joined), strip_ansi);
             if joined_feedback.is_some() {
                 return joined_feedback;
             }
             for item in items {
-                if let Some(nested) = extract_console_feedback_from_value(item, depth + 1) {
+                if let Some(nested) =
+                    extract_console_feedback_from_value(item, depth + 1, strip_ansi)
+                {
                     return Some(nested);
                 }
             }
@@ -185,6 +300,7 @@ fn extract_console_feedback_from_value(payload: &Value, depth: usize) -> Option<
                     .or_else(|| map.get("message").and_then(value_as_non_empty_string))
                     .or_else(|| map.get("text").and_then(value_as_non_empty_string))
                     .or_else(|| map.get("status").and_then(value_as_non_empty_string)),
+                strip_ansi,
             );
             if direct.is_some() {
                 return direct;
@@ -195,7 +311,9 @@ fn extract_console_feedback_from_value(payload: &Value, depth: usize) -> Option<
                 "error", "errors", "log", "logs", "lines", "data", "payload",
             ] {
                 if let Some(value) = map.get(key) {
-                    if let Some(nested) = extract_console_feedback_from_value(value, depth + 1) {
+                    if let Some(nested) =
+                        extract_console_feedback_from_value(value, depth + 1, strip_ansi)
+                    {
                         return Some(nested);
                     }
                 }
@@ -206,8 +324,52 @@ fn extract_console_feedback_from_value(payload: &Value, depth: usize) -> Option<
     }
 }
 
-fn extract_console_feedback(payload: &Value) -> Option<String> {
-    extract_console_feedback_from_value(payload, 0)
+fn extract_console_feedback(payload: &Value, strip_ansi: bool) -> Option<String> {
+    extract_console_feedback_from_value(payload, 0, strip_ansi)
+}
+
+fn collect_console_lines(value: Option<&Value>, lines: &mut Vec<String>, strip_ansi: bool) {
+    match value {
+        Some(Value::Array(items)) => {
+            for item in items {
+                collect_console_lines(Some(item), lines, strip_ansi);
+            }
+        }
+        Some(Value::String(text)) => {
+            let text = if strip_ansi { strip_ansi_codes(text) } else { text.clone() };
+            lines.extend(
+                text.lines()
+                    .map(str::trim_end)
+                    .filter(|line| !line.trim().is_empty())
+                    .map(str::to_string),
+            );
+        }
+        _ => {}
+    }
+}
+
+fn extract_console_lines(payload: &Value, strip_ansi: bool) -> (Vec<String>, Vec<String>) {
+    let mut log_lines = Vec::new();
+    let mut error_lines = Vec::new();
+    let mut stack = vec![payload];
+    while let Some(current) = stack.pop() {
+        let Value::Object(map) = current else {
+            continue;
+        };
+        if let Some(messages) = map.get("messages").filter(|value| value.is_object()) {
+            collect_console_lines(messages.get("log"), &mut log_lines, strip_ansi);
+            collect_console_lines(messages.get("results"), &mut log_lines, strip_ansi);
+            collect_console_lines(messages.get("error"), &mut error_lines, strip_ansi);
+            collect_console_lines(map.get("error"), &mut error_lines, strip_ansi);
+            continue;
+        }
+        for key in ["result", "data", "payload"] {
+            if let Some(value) = map.get(key) {
+                stack.push(value);
+            }
+        }
+    }
+    (log_lines, error_lines)
 }
 
 type ConsoleRequestCandidate = (String, Option<HashMap<String, Value>>, Value);
@@ -215,10 +377,13 @@ type ConsoleRequestCandidate = (String, Option<HashMap<String, Value>>, Value);
 fn build_console_request_candidates(
     code: &str,
     shard: Option<&str>,
+    known_shards: Option<Vec<String>>,
 ) -> Vec<ConsoleRequestCandidate> {
     let mut candidates: Vec<ConsoleRequestCandidate> = Vec::new();
     let shard_values: Vec<String> = if let Some(value) = shard {
         vec![value.to_string()]
+    } else if let Some(names) = known_shards {
+        names
     } else {
         vec!["shard0".to_string(), "shard1".to_string(), "shard2".to_string(), "shard3".to_string()]
     };
@@ -265,15 +430,16 @@ fn build_console_request_candidates(
     candidates
 }
 
-#[tauri::command]
-pub async fn screeps_console_execute(
-    request: ScreepsConsoleExecuteRequest,
+async fn execute_console_command(
+    request: &ScreepsConsoleExecuteRequest,
 ) -> Result<ScreepsConsoleExecuteResponse, String> {
     let trimmed_code = request.code.trim();
     if trimmed_code.is_empty() {
         return Ok(ScreepsConsoleExecuteResponse {
             ok: false,
             feedback: None,
+            log_lines: Vec::new(),
+            error_lines: Vec::new(),
             error: Some("Console command cannot be empty.".to_string()),
             used_variant: None,
             tried_variants: Vec::new(),
@@ -281,8 +447,28 @@ pub async fn screeps_console_execute(
     }
     let client = shared_http_client()?;
 
-    let shard = normalize_console_shard(request.shard.as_deref());
-    let candidates = build_console_request_candidates(trimmed_code, shard.as_deref());
+    let single_shard =
+        is_single_shard_server(&request.base_url, Some(&request.token), Some(&request.username))
+            .await;
+    let shard =
+        normalize_shard_for(&request.base_url, request.shard.as_deref()).filter(|_| !single_shard);
+    let strip_ansi = !request.preserve_ansi.unwrap_or(false);
+    let known_shards = match shard {
+        Some(_) => None,
+        None if single_shard => Some(Vec::new()),
+        None => {
+            shard_names_for(&request.base_url, Some(&request.token), Some(&request.username)).await
+        }
+    };
+    let mut candidates =
+        build_console_request_candidates(trimmed_code, shard.as_deref(), known_shards);
+    // Try the variant that last worked for this server first; the rest stay as fallbacks.
+    if let Some(position) = remembered_console_variant(&request.base_url)
+        .and_then(|remembered| candidates.iter().position(|(variant, _, _)| *variant == remembered))
+    {
+        let remembered = candidates.remove(position);
+        candidates.insert(0, remembered);
+    }
     let mut failures: Vec<String> = Vec::new();
     let mut tried_variants: Vec<String> = Vec::with_capacity(candidates.len());
 
@@ -296,9 +482,10 @@ pub async fn screeps_console_execute(
             username: Some(request.username.clone()),
             query,
             body: Some(body),
+            ..Default::default()
         };
 
-        let response = match perform_screeps_request(client, raw_request).await {
+        let response = match perform_screeps_request(&client, raw_request).await {
             Ok(response) => response,
             Err(error) => {
                 failures.push(error);
@@ -318,21 +505,127 @@ pub async fn screeps_console_execute(
             continue;
         }
 
+        remember_console_variant(&request.base_url, Some(&variant));
+        let (log_lines, error_lines) = extract_console_lines(&response.data, strip_ansi);
         return Ok(ScreepsConsoleExecuteResponse {
             ok: true,
-            feedback: extract_console_feedback(&response.data),
+            feedback: extract_console_feedback(&response.data, strip_ansi),
+            log_lines,
+            error_lines,
             error: None,
             used_variant: Some(variant),
             tried_variants,
         });
     }
 
+    remember_console_variant(&request.base_url, None);
     let reason = failures.into_iter().next().unwrap_or_else(|| "Unknown error".to_string());
     Ok(ScreepsConsoleExecuteResponse {
         ok: false,
         feedback: None,
+        log_lines: Vec::new(),
+        error_lines: Vec::new(),
         error: Some(format!("Failed to execute console command: {}", reason)),
         used_variant: None,
         tried_variants,
     })
 }
+
+#[tauri::command]
+pub async fn screeps_console_execute(
+    request: ScreepsConsoleExecuteRequest,
+) -> Result<ScreepsConsoleExecuteResponse, String> {
+    let response = execute_console_command(&request).await?;
+    if !request.code.trim().is_empty() {
+        record_console_history(&request, &response);
+    }
+    Ok(response)
+}
+
+#[tauri::command]
+pub async fn screeps_console_execute_batch(
+    request: ScreepsConsoleBatchRequest,
+) -> Result<Vec<ScreepsConsoleExecuteResponse>, String> {
+    let mut responses = Vec::with_capacity(request.commands.len());
+    for code in request.commands {
+        let command_request = ScreepsConsoleExecuteRequest {
+            base_url: request.base_url.clone(),
+            token: request.token.clone(),
+            username: request.username.clone(),
+            code,
+            shard: request.shard.clone(),
+            preserve_ansi: request.preserve_ansi,
+        };
+        let response = execute_console_command(&command_request).await?;
+        if !command_request.code.trim().is_empty() {
+            record_console_history(&command_request, &response);
+        }
+        let failed = !response.ok;
+        responses.push(response);
+        if failed && request.stop_on_error {
+            break;
+        }
+    }
+    Ok(responses)
+}
+
+#[tauri::command]
+pub fn screeps_console_history(request: ScreepsConsoleHistoryRequest) -> Vec<ConsoleHistoryEntry> {
+    let key = console_history_key(&request.base_url, &request.username);
+    console_history()
+        .lock()
+        .ok()
+        .and_then(|guard| guard.get(&key).map(|entries| entries.iter().cloned().collect()))
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn screeps_console_history_clear(request: ScreepsConsoleHistoryRequest) -> usize {
+    let key = console_history_key(&request.base_url, &request.username);
+    console_history()
+        .lock()
+        .ok()
+        .and_then(|mut guard| guard.remove(&key))
+        .map(|entries| entries.len())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_color_and_reset_sequences() {
+        let colored = "\u{1b}[31m[E1] spawn failed: ERR_NOT_ENOUGH_ENERGY\u{1b}[0m";
+        assert_eq!(strip_ansi_codes(colored), "[E1] spawn failed: ERR_NOT_ENOUGH_ENERGY");
+    }
+
+    #[test]
+    fn strips_multi_parameter_sequences() {
+        let colored =
+            "\u{1b}[1;33mW1N1\u{1b}[0m: \u{1b}[38;5;82mRCL 7\u{1b}[39m (\u{9b}2mdim\u{9b}0m)";
+        assert_eq!(strip_ansi_codes(colored), "W1N1: RCL 7 (dim)");
+    }
+
+    #[test]
+    fn handles_truncated_and_unterminated_escapes() {
+        assert_eq!(strip_ansi_codes("cpu 12.5\u{1b}[1;3"), "cpu 12.5");
+        assert_eq!(strip_ansi_codes("bucket 10000\u{1b}"), "bucket 10000");
+        assert_eq!(strip_ansi_codes("\u{1b}[mplain"), "plain");
+        assert_eq!(strip_ansi_codes("a\u{1b}Mb"), "ab");
+    }
+
+    #[test]
+    fn sanitize_strips_ansi_unless_preserved() {
+        let colored = "\u{1b}[32mcreeps: 42\u{1b}[0m".to_string();
+        assert_eq!(
+            sanitize_console_feedback(Some(colored.clone()), true).as_deref(),
+            Some("creeps: 42")
+        );
+        assert_eq!(
+            sanitize_console_feedback(Some(colored.clone()), false).as_deref(),
+            Some(colored.as_str())
+        );
+        assert_eq!(sanitize_console_feedback(Some("\u{1b}[0m\u{1b}[32m".to_string()), true), None);
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::http::ScreepsResponse;
+
+static DISK_CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// (method, endpoint, ttl): terrain never changes, map-stats ownership drifts slowly.
+const DISK_CACHE_ENDPOINTS: [(&str, &str, u64); 2] = [
+    ("GET", "/api/game/room-terrain", 7 * 24 * 60 * 60 * 1_000),
+    ("POST", "/api/game/map-stats", 10 * 60 * 1_000),
+];
+const DISK_CACHE_MAX_BYTES: u64 = 32 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskCacheEntry {
+    key: String,
+    stored_at_ms: u64,
+    response: ScreepsResponse,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+pub(crate) fn set_disk_cache_dir(dir: PathBuf) {
+    let _ = DISK_CACHE_DIR.set(dir);
+}
+
+pub(crate) fn disk_cache_ttl_ms(method: &str, endpoint: &str) -> Option<u64> {
+    DISK_CACHE_ENDPOINTS
+        .iter()
+        .find(|(candidate_method, candidate, _)| {
+            method.eq_ignore_ascii_case(candidate_method)
+                && endpoint.eq_ignore_ascii_case(candidate)
+        })
+        .map(|(_, _, ttl_ms)| *ttl_ms)
+}
+
+// Only the fields that select map-stats data; room order must not split the cache.
+fn body_key(body: Option<&Value>) -> String {
+    let Some(body) = body else {
+        return String::new();
+    };
+    let mut rooms = body
+        .get("rooms")
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).collect::<Vec<&str>>())
+        .unwrap_or_default();
+    rooms.sort_unstable();
+    json!({
+        "rooms": rooms,
+        "statName": body.get("statName"),
+        "shard": body.get("shard"),
+    })
+    .to_string()
+}
+
+// Disk-cached endpoints serve public, slow-moving data, so the key leaves out the token and
+// username.
+pub(crate) fn disk_cache_key(
+    method: &str,
+    base_url: &str,
+    endpoint: &str,
+    query_pairs: &[(String, String)],
+    body: Option<&Value>,
+) -> String {
+    let query_part = serde_json::to_string(query_pairs).unwrap_or_else(|_| "[]".to_string());
+    let method = method.to_ascii_uppercase();
+    let body_part = if method == "GET" { String::new() } else { body_key(body) };
+    format!(
+        "{}|{}|{}|{}|{}",
+        method,
+        base_url,
+        endpoint.to_ascii_lowercase(),
+        query_part,
+        body_part
+    )
+}
+
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    dir.join(format!("{:016x}.json", hasher.finish()))
+}
+
+pub(crate) fn read_disk_cache(key: &str, ttl_ms: u64) -> Option<ScreepsResponse> {
+    let path = entry_path(DISK_CACHE_DIR.get()?, key);
+    let bytes = fs::read(&path).ok()?;
+    let entry = serde_json::from_slice::<DiskCacheEntry>(&bytes).ok();
+    match entry {
+        Some(entry) if entry.key == key => {
+            if now_millis().saturating_sub(entry.stored_at_ms) > ttl_ms {
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+            Some(entry.response)
+        }
+        // Hash collision: leave the other key's entry alone.
+        Some(_) => None,
+        None => {
+            let _ = fs::remove_file(&path);
+            None
+        }
+    }
+}
+
+pub(crate) fn write_disk_cache(key: &str, response: &ScreepsResponse) {
+    let Some(dir) = DISK_CACHE_DIR.get() else {
+        return;
+    };
+    // Screeps reports many failures as 200 with an `error` field; never persist those.
+    if !response.ok || response.data.get("error").is_some() || fs::create_dir_all(dir).is_err() {
+        return;
+    }
+    let entry = DiskCacheEntry {
+        key: key.to_string(),
+        stored_at_ms: now_millis(),
+        response: ScreepsResponse {
+            duration_ms: None,
+            from_cache: false,
+            token: None,
+            ..response.clone()
+        },
+    };
+    let Ok(bytes) = serde_json::to_vec(&entry) else {
+        return;
+    };
+    if fs::write(entry_path(dir, key), bytes).is_ok() {
+        evict_oversized(dir);
+    }
+}
+
+fn evict_oversized(dir: &Path) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut files = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|item| {
+            let metadata = item.metadata().ok().filter(|metadata| metadata.is_file())?;
+            Some((item.path(), metadata.len(), metadata.modified().unwrap_or(UNIX_EPOCH)))
+        })
+        .collect::<Vec<_>>();
+    let mut total = files.iter().map(|(_, len, _)| len).sum::<u64>();
+    if total <= DISK_CACHE_MAX_BYTES {
+        return;
+    }
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (path, len, _) in files {
+        if total <= DISK_CACHE_MAX_BYTES {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(len);
+        }
+    }
+}
+
+pub(crate) fn purge_disk_cache() -> usize {
+    let Some(dir) = DISK_CACHE_DIR.get() else {
+        return 0;
+    };
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return 0;
+    };
+    read_dir
+        .filter_map(Result::ok)
+        .filter(|item| item.path().extension().is_some_and(|extension| extension == "json"))
+        .filter(|item| fs::remove_file(item.path()).is_ok())
+        .count()
+}
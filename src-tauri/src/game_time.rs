@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::http::{
+    normalize_base_url, perform_screeps_request, request_url, shared_http_client, ScreepsRequest,
+};
+use crate::rooms::normalize_shard_for;
+
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+static GAME_TIME_OBSERVATIONS: OnceLock<Mutex<HashMap<String, Vec<GameTimeObservation>>>> =
+    OnceLock::new();
+
+#[derive(Debug, Clone, Copy)]
+struct GameTimeObservation {
+    time: u64,
+    observed_at: Instant,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsGameTimeRequest {
+    pub base_url: String,
+    pub token: Option<String>,
+    pub username: Option<String>,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsGameTimeResponse {
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsTickRateResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_duration_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticks_per_second: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsPingResponse {
+    pub reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_time: Option<u64>,
+}
+
+fn game_time_observations() -> &'static Mutex<HashMap<String, Vec<GameTimeObservation>>> {
+    GAME_TIME_OBSERVATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn observation_key(base_url: &str, shard: Option<&str>) -> String {
+    format!("{}|{}", normalize_base_url(base_url), shard.unwrap_or_default())
+}
+
+fn record_observation(key: String, time: u64) {
+    let Ok(mut guard) = game_time_observations().lock() else {
+        return;
+    };
+    let observations = guard.entry(key).or_default();
+    // Repeated readings of the same tick would shrink the window without adding information.
+    if observations.last().is_some_and(|last| last.time == time) {
+        return;
+    }
+    observations.push(GameTimeObservation { time, observed_at: Instant::now() });
+    if observations.len() > 2 {
+        observations.remove(0);
+    }
+}
+
+#[tauri::command]
+pub async fn screeps_game_time(
+    request: ScreepsGameTimeRequest,
+) -> Result<ScreepsGameTimeResponse, String> {
+    Ok(ScreepsGameTimeResponse { time: fetch_game_time(request).await? })
+}
+
+pub(crate) async fn fetch_game_time(request: ScreepsGameTimeRequest) -> Result<u64, String> {
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let query = shard.as_ref().map(|shard| HashMap::from([("shard".to_string(), json!(shard))]));
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url.clone(),
+            endpoint: "/api/game/time".to_string(),
+            method: Some("GET".to_string()),
+            token: request.token,
+            username: request.username,
+            query,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("game time request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = response.data.get("error").and_then(Value::as_str) {
+        return Err(error.trim().to_string());
+    }
+    let time = response
+        .data
+        .get("time")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "game time response did not include a time".to_string())?;
+
+    if !response.from_cache {
+        record_observation(observation_key(&request.base_url, shard.as_deref()), time);
+    }
+    Ok(time)
+}
+
+#[tauri::command]
+pub fn screeps_tick_rate(request: ScreepsGameTimeRequest) -> ScreepsTickRateResponse {
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let key = observation_key(&request.base_url, shard.as_deref());
+    let tick_duration_ms = game_time_observations().lock().ok().and_then(|guard| {
+        let [previous, latest] = guard.get(&key)?.as_slice() else {
+            return None;
+        };
+        let ticks = latest.time.checked_sub(previous.time).filter(|ticks| *ticks > 0)?;
+        let elapsed_ms =
+            latest.observed_at.duration_since(previous.observed_at).as_secs_f64() * 1_000.0;
+        Some(elapsed_ms / ticks as f64)
+    });
+    ScreepsTickRateResponse {
+        tick_duration_ms,
+        ticks_per_second: tick_duration_ms
+            .filter(|duration| *duration > 0.0)
+            .map(|duration| 1_000.0 / duration),
+    }
+}
+
+// Goes straight to the client so the probe has its own timeout and never touches the response cache.
+#[tauri::command]
+pub async fn screeps_ping(request: ScreepsGameTimeRequest) -> Result<ScreepsPingResponse, String> {
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let url = request_url(&ScreepsRequest {
+        base_url: request.base_url.clone(),
+        endpoint: "/api/game/time".to_string(),
+        ..Default::default()
+    });
+    let client = shared_http_client()?;
+    let mut req = client.get(url).timeout(PING_TIMEOUT);
+    if let Some(shard) = shard.as_deref() {
+        req = req.query(&[("shard", shard)]);
+    }
+    if let Some(token) = request.token.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        req = req.header("X-Token", token);
+    }
+    if let Some(username) =
+        request.username.as_deref().map(str::trim).filter(|value| !value.is_empty())
+    {
+        req = req.header("X-Username", username);
+    }
+
+    let started_at = Instant::now();
+    let Ok(response) = req.send().await else {
+        return Ok(ScreepsPingResponse { reachable: false, latency_ms: None, server_time: None });
+    };
+    let reachable = !response.status().is_server_error();
+    let payload = response.json::<Value>().await.ok();
+    Ok(ScreepsPingResponse {
+        reachable,
+        latency_ms: Some(started_at.elapsed().as_millis() as u64),
+        server_time: payload.as_ref().and_then(|data| data.get("time")).and_then(Value::as_u64),
+    })
+}
@@ -1,11 +1,21 @@
-use reqwest::{Client, Method};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING};
+use reqwest::{Client, Method, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Mutex, OnceLock};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast;
 
-#[derive(Debug, Deserialize, Clone)]
+use crate::disk_cache::{disk_cache_key, disk_cache_ttl_ms, read_disk_cache, write_disk_cache};
+use crate::rate_limit::{acquire_request_slot, record_rate_limited};
+
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ScreepsRequest {
     pub base_url: String,
@@ -15,27 +25,149 @@ pub struct ScreepsRequest {
     pub username: Option<String>,
     pub query: Option<HashMap<String, Value>>,
     pub body: Option<Value>,
+    pub max_retries: Option<u32>,
+    pub retry_base_delay_ms: Option<u64>,
+    pub retryable: Option<bool>,
+    pub headers: Option<HashMap<String, String>>,
+    pub allow_reserved_headers: Option<bool>,
+    pub priority: Option<i32>,
+    pub path_prefix: Option<String>,
+    pub dry_run: Option<bool>,
+    pub idempotency_key: Option<String>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ScreepsResponse {
     pub status: u16,
     pub ok: bool,
     pub data: Value,
     pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    pub from_cache: bool,
+    pub raw_bytes_len: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    // Set when a retryable failure is still failing after every retry was spent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u32>,
 }
 
-static HTTP_CLIENT: OnceLock<Result<Client, String>> = OnceLock::new();
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientTimeouts {
+    pub connect_ms: u64,
+    pub request_ms: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLogEntry {
+    pub timestamp: u64,
+    pub method: String,
+    pub endpoint: String,
+    pub status: u16,
+    pub duration_ms: u64,
+    pub from_cache: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_in_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct CircuitBreaker {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseLimits {
+    pub max_response_bytes: u64,
+}
+
+static REQUEST_LOG: OnceLock<Mutex<VecDeque<RequestLogEntry>>> = OnceLock::new();
+static HTTP_CLIENT: OnceLock<Mutex<Option<Client>>> = OnceLock::new();
+static CLIENT_TIMEOUTS: OnceLock<Mutex<ClientTimeouts>> = OnceLock::new();
+static USER_AGENT: OnceLock<String> = OnceLock::new();
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static PATH_PREFIXES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
 static RESPONSE_CACHE: OnceLock<Mutex<HashMap<String, ResponseCacheEntry>>> = OnceLock::new();
+static RESPONSE_CACHE_TTL_OVERRIDES: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+static IDEMPOTENT_RESPONSES: OnceLock<Mutex<HashMap<String, (Instant, ScreepsResponse)>>> =
+    OnceLock::new();
+static CIRCUIT_BREAKERS: OnceLock<Mutex<HashMap<String, CircuitBreaker>>> = OnceLock::new();
+static INFLIGHT_REQUESTS: OnceLock<Mutex<HashMap<String, broadcast::Sender<InflightResult>>>> =
+    OnceLock::new();
 
 const RESPONSE_CACHE_DEFAULT_TTL_MS: u64 = 1_800;
 const RESPONSE_CACHE_TERRAIN_TTL_SECS: u64 = 900;
 const RESPONSE_CACHE_MAX_ENTRIES: usize = 2_048;
+const RESPONSE_CACHE_STALE_RETENTION_SECS: u64 = 3_600;
+const DEFAULT_CLIENT_TIMEOUTS: ClientTimeouts =
+    ClientTimeouts { connect_ms: 8_000, request_ms: 20_000 };
+const MIN_CONNECT_TIMEOUT_MS: u64 = 1_000;
+const MAX_CONNECT_TIMEOUT_MS: u64 = 30_000;
+const MIN_REQUEST_TIMEOUT_MS: u64 = 1_000;
+const MAX_REQUEST_TIMEOUT_MS: u64 = 120_000;
+const DEFAULT_USER_AGENT: &str = "screeps-dashboard/0.1.0";
+const RESERVED_HEADERS: [&str; 6] =
+    ["x-token", "x-username", "accept", "accept-encoding", "content-type", "if-none-match"];
+const DEFAULT_MAX_RETRIES: u32 = 2;
+const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 250;
+const MAX_RETRIES_LIMIT: u32 = 8;
+const MAX_RETRY_DELAY_MS: u64 = 10_000;
+const MAX_PAGINATE_PAGES: usize = 100;
+const MIN_REDACTED_SECRET_LEN: usize = 4;
+const DRY_RUN_VISIBLE_HEADERS: [&str; 5] =
+    ["content-type", "accept", "accept-encoding", "user-agent", "x-username"];
+const REQUEST_LOG_CAPACITY: usize = 500;
+const IDEMPOTENCY_WINDOW_SECS: u64 = 120;
+const DEFAULT_MAX_RESPONSE_BYTES: u64 = 16 * 1024 * 1024;
+const MIN_MAX_RESPONSE_BYTES: u64 = 64 * 1024;
+const MAX_MAX_RESPONSE_BYTES: u64 = 512 * 1024 * 1024;
+const CIRCUIT_FAILURE_THRESHOLD: u32 = 5;
+const CIRCUIT_COOLDOWN_SECS: u64 = 30;
+
+static JITTER_STATE: AtomicU64 = AtomicU64::new(0);
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+static CACHE_EVICTIONS: AtomicU64 = AtomicU64::new(0);
+static REQUEST_LOGGING: AtomicBool = AtomicBool::new(false);
+static MAX_RESPONSE_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_MAX_RESPONSE_BYTES);
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub approx_bytes: usize,
+}
 
 #[derive(Debug, Clone)]
 struct ResponseCacheEntry {
+    base_url: String,
+    endpoint: String,
     response: ScreepsResponse,
+    etag: Option<String>,
     expires_at: Instant,
 }
 
@@ -48,6 +180,47 @@ pub(crate) fn normalize_base_url(base_url: &str) -> String {
     }
 }
 
+fn normalize_path_prefix(prefix: &str) -> String {
+    let trimmed = prefix.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+fn path_prefixes() -> &'static Mutex<HashMap<String, String>> {
+    PATH_PREFIXES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub(crate) fn set_path_prefix(base_url: &str, prefix: &str) -> Result<String, String> {
+    let prefix = normalize_path_prefix(prefix);
+    if prefix.contains(['?', '#']) || contains_line_break(&prefix) {
+        return Err(format!("Invalid path prefix: {}", prefix));
+    }
+    let mut guard = path_prefixes().lock().map_err(|_| "path prefix lock poisoned".to_string())?;
+    if prefix.is_empty() {
+        guard.remove(&normalize_base_url(base_url));
+    } else {
+        guard.insert(normalize_base_url(base_url), prefix.clone());
+    }
+    Ok(prefix)
+}
+
+// Base URL plus the reverse-proxy prefix; cache and in-flight keys are built from this.
+fn request_base_url(request: &ScreepsRequest) -> String {
+    let base_url = normalize_base_url(&request.base_url);
+    let prefix = match request.path_prefix.as_deref() {
+        Some(prefix) => normalize_path_prefix(prefix),
+        None => path_prefixes()
+            .lock()
+            .ok()
+            .and_then(|guard| guard.get(&base_url).cloned())
+            .unwrap_or_default(),
+    };
+    format!("{}{}", base_url, prefix)
+}
+
 pub(crate) fn normalize_endpoint(endpoint: &str) -> String {
     if endpoint.starts_with('/') {
         endpoint.to_string()
@@ -60,7 +233,31 @@ fn response_cache() -> &'static Mutex<HashMap<String, ResponseCacheEntry>> {
     RESPONSE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
+fn response_cache_ttl_overrides() -> &'static Mutex<HashMap<String, u64>> {
+    RESPONSE_CACHE_TTL_OVERRIDES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub(crate) fn configure_cache_ttls(ttls: HashMap<String, u64>) -> HashMap<String, u64> {
+    let normalized = ttls
+        .into_iter()
+        .map(|(endpoint, ttl_ms)| {
+            (normalize_endpoint(endpoint.trim()).to_ascii_lowercase(), ttl_ms)
+        })
+        .collect::<HashMap<String, u64>>();
+    if let Ok(mut guard) = response_cache_ttl_overrides().lock() {
+        *guard = normalized.clone();
+    }
+    normalized
+}
+
 fn cache_ttl_for_endpoint(endpoint: &str) -> Duration {
+    if let Some(ttl_ms) = response_cache_ttl_overrides()
+        .lock()
+        .ok()
+        .and_then(|guard| guard.get(&endpoint.to_ascii_lowercase()).copied())
+    {
+        return Duration::from_millis(ttl_ms);
+    }
     if endpoint.eq_ignore_ascii_case("/api/game/room-terrain") {
         Duration::from_secs(RESPONSE_CACHE_TERRAIN_TTL_SECS)
     } else {
@@ -68,20 +265,146 @@ fn cache_ttl_for_endpoint(endpoint: &str) -> Duration {
     }
 }
 
-pub(crate) fn shared_http_client() -> Result<&'static Client, String> {
-    HTTP_CLIENT
-        .get_or_init(|| {
-            Client::builder()
-                .connect_timeout(Duration::from_secs(8))
-                .timeout(Duration::from_secs(20))
-                .pool_idle_timeout(Duration::from_secs(90))
-                .pool_max_idle_per_host(16)
-                .user_agent("screeps-dashboard/0.1.0")
-                .build()
-                .map_err(|error| format!("failed to initialize http client: {}", error))
-        })
-        .as_ref()
-        .map_err(|error| error.clone())
+fn http_client_slot() -> &'static Mutex<Option<Client>> {
+    HTTP_CLIENT.get_or_init(|| Mutex::new(None))
+}
+
+fn client_timeouts() -> &'static Mutex<ClientTimeouts> {
+    CLIENT_TIMEOUTS.get_or_init(|| Mutex::new(DEFAULT_CLIENT_TIMEOUTS))
+}
+
+fn build_http_client(timeouts: ClientTimeouts) -> Result<Client, String> {
+    Client::builder()
+        .connect_timeout(Duration::from_millis(timeouts.connect_ms))
+        .timeout(Duration::from_millis(timeouts.request_ms))
+        .pool_idle_timeout(Duration::from_secs(90))
+        .pool_max_idle_per_host(16)
+        .user_agent(USER_AGENT.get().map(String::as_str).unwrap_or(DEFAULT_USER_AGENT))
+        .default_headers(HeaderMap::from_iter([(
+            ACCEPT_ENCODING,
+            HeaderValue::from_static("gzip, br"),
+        )]))
+        .build()
+        .map_err(|error| format!("failed to initialize http client: {}", error))
+}
+
+pub(crate) fn shared_http_client() -> Result<Client, String> {
+    let mut guard =
+        http_client_slot().lock().map_err(|_| "http client lock poisoned".to_string())?;
+    if let Some(client) = guard.as_ref() {
+        return Ok(client.clone());
+    }
+    let timeouts = client_timeouts().lock().map(|guard| *guard).unwrap_or(DEFAULT_CLIENT_TIMEOUTS);
+    let client = build_http_client(timeouts)?;
+    *guard = Some(client.clone());
+    Ok(client)
+}
+
+fn reset_http_client() {
+    if let Ok(mut guard) = http_client_slot().lock() {
+        *guard = None;
+    }
+}
+
+pub(crate) fn configure_client_timeouts(
+    connect_ms: u64,
+    request_ms: u64,
+) -> Result<ClientTimeouts, String> {
+    let timeouts = ClientTimeouts {
+        connect_ms: connect_ms.clamp(MIN_CONNECT_TIMEOUT_MS, MAX_CONNECT_TIMEOUT_MS),
+        request_ms: request_ms.clamp(MIN_REQUEST_TIMEOUT_MS, MAX_REQUEST_TIMEOUT_MS),
+    };
+    // Build first so a failure leaves the current client and timeouts untouched.
+    let client = build_http_client(timeouts)?;
+    if let Ok(mut guard) = client_timeouts().lock() {
+        *guard = timeouts;
+    }
+    if let Ok(mut guard) = http_client_slot().lock() {
+        *guard = Some(client);
+    }
+    Ok(timeouts)
+}
+
+pub(crate) fn configure_response_limits(max_response_bytes: Option<u64>) -> ResponseLimits {
+    if let Some(bytes) = max_response_bytes {
+        MAX_RESPONSE_BYTES
+            .store(bytes.clamp(MIN_MAX_RESPONSE_BYTES, MAX_MAX_RESPONSE_BYTES), Ordering::Relaxed);
+    }
+    ResponseLimits { max_response_bytes: MAX_RESPONSE_BYTES.load(Ordering::Relaxed) }
+}
+
+fn contains_line_break(value: &str) -> bool {
+    value.contains(['\r', '\n'])
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScreepsTokenRefreshedEvent {
+    base_url: String,
+    token: String,
+}
+
+pub(crate) fn set_app_handle(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+// The official server rotates X-Token on some calls; the frontend must persist the new one.
+fn emit_token_refreshed(base_url: &str, request_token: Option<&str>, refreshed: Option<&str>) {
+    let Some(refreshed) = refreshed.map(str::trim).filter(|value| !value.is_empty()) else {
+        return;
+    };
+    if request_token == Some(refreshed) {
+        return;
+    }
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit(
+            "screeps://token-refreshed",
+            ScreepsTokenRefreshedEvent {
+                base_url: base_url.to_string(),
+                token: refreshed.to_string(),
+            },
+        );
+    }
+}
+
+pub(crate) fn set_user_agent(user_agent: &str) -> Result<String, String> {
+    let user_agent = user_agent.trim();
+    if user_agent.is_empty() {
+        return Err("User agent cannot be empty".to_string());
+    }
+    if contains_line_break(user_agent) {
+        return Err("User agent must not contain line breaks".to_string());
+    }
+    USER_AGENT
+        .set(user_agent.to_string())
+        .map_err(|_| "User agent has already been set".to_string())?;
+    reset_http_client();
+    Ok(user_agent.to_string())
+}
+
+fn build_custom_headers(request: &ScreepsRequest) -> Result<HeaderMap, String> {
+    let mut headers = HeaderMap::new();
+    let Some(custom) = request.headers.as_ref() else {
+        return Ok(headers);
+    };
+    let allow_reserved = request.allow_reserved_headers.unwrap_or(false);
+    for (name, value) in custom {
+        let name = name.trim();
+        if contains_line_break(name) || contains_line_break(value) {
+            return Err(format!("header {} must not contain line breaks", name));
+        }
+        if !allow_reserved
+            && RESERVED_HEADERS.iter().any(|reserved| name.eq_ignore_ascii_case(reserved))
+        {
+            continue;
+        }
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|error| format!("invalid header name {}: {}", name, error))?;
+        let header_value = HeaderValue::from_str(value.trim())
+            .map_err(|error| format!("invalid value for header {}: {}", name, error))?;
+        headers.insert(header_name, header_value);
+    }
+    Ok(headers)
 }
 
 fn serialize_query_value(value: &Value) -> Option<String> {
@@ -106,6 +429,67 @@ fn build_query_pairs(query: &HashMap<String, Value>) -> Vec<(String, String)> {
     query_pairs
 }
 
+fn dry_run_response(
+    request: &ScreepsRequest,
+    method: &Method,
+    url: String,
+    query_pairs: &[(String, String)],
+    custom_headers: &HeaderMap,
+) -> ScreepsResponse {
+    let mut headers = serde_json::Map::new();
+    headers.insert("Accept".to_string(), json!("application/json"));
+    headers.insert(
+        "User-Agent".to_string(),
+        json!(USER_AGENT.get().map(String::as_str).unwrap_or(DEFAULT_USER_AGENT)),
+    );
+    if request.token.as_deref().is_some_and(|token| !token.trim().is_empty()) {
+        headers.insert("X-Token".to_string(), json!("***"));
+    }
+    if let Some(username) = request.username.as_deref().map(str::trim) {
+        if !username.is_empty() {
+            headers.insert("X-Username".to_string(), json!(username));
+        }
+    }
+    // Custom headers may carry credentials (Authorization, cookies, proxy secrets); only echo
+    // values that are known to be harmless.
+    for (name, value) in custom_headers {
+        let value = if DRY_RUN_VISIBLE_HEADERS
+            .iter()
+            .any(|visible| name.as_str().eq_ignore_ascii_case(visible))
+        {
+            value.to_str().unwrap_or_default()
+        } else {
+            "***"
+        };
+        headers.insert(name.as_str().to_string(), json!(value));
+    }
+    let body = if *method == Method::GET { None } else { request.body.clone() };
+    let token = request.token.as_deref();
+    let url = redact_secrets(&url, token);
+    let query_pairs = query_pairs
+        .iter()
+        .map(|(key, value)| (key.clone(), redact_secrets(value, token)))
+        .collect::<Vec<(String, String)>>();
+
+    ScreepsResponse {
+        status: 0,
+        ok: false,
+        data: json!({
+            "method": method.as_str(),
+            "url": url,
+            "query": query_pairs,
+            "headers": headers,
+            "body": body,
+        }),
+        url,
+        duration_ms: None,
+        from_cache: false,
+        raw_bytes_len: 0,
+        token: None,
+        attempts: None,
+    }
+}
+
 fn build_response_cache_key(
     request: &ScreepsRequest,
     base_url: &str,
@@ -115,18 +499,87 @@ fn build_response_cache_key(
     let query_part = serde_json::to_string(query_pairs).unwrap_or_else(|_| "[]".to_string());
     let token = request.token.as_deref().map(str::trim).unwrap_or("");
     let username = request.username.as_deref().map(str::trim).unwrap_or("");
-    format!("GET|{}|{}|{}|{}|{}", base_url, endpoint, query_part, token, username)
+    let mut key = format!("GET|{}|{}|{}|{}|{}", base_url, endpoint, query_part, token, username);
+    if let Some(headers) = request.headers.as_ref().filter(|headers| !headers.is_empty()) {
+        let mut header_pairs = headers
+            .iter()
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim()))
+            .collect::<Vec<_>>();
+        header_pairs.sort_unstable();
+        key.push('|');
+        key.push_str(&serde_json::to_string(&header_pairs).unwrap_or_default());
+    }
+    key
+}
+
+fn is_cache_entry_retained(entry: &ResponseCacheEntry, now: Instant) -> bool {
+    if entry.expires_at > now {
+        return true;
+    }
+    entry.etag.is_some()
+        && entry.expires_at + Duration::from_secs(RESPONSE_CACHE_STALE_RETENTION_SECS) > now
+}
+
+fn prune_cache_entries(guard: &mut HashMap<String, ResponseCacheEntry>, now: Instant) {
+    let before = guard.len();
+    guard.retain(|_, entry| is_cache_entry_retained(entry, now));
+    CACHE_EVICTIONS.fetch_add((before - guard.len()) as u64, Ordering::Relaxed);
+}
+
+pub(crate) fn response_cache_stats() -> CacheStats {
+    let (entries, approx_bytes) = response_cache()
+        .lock()
+        .map(|guard| {
+            let bytes = guard
+                .values()
+                .map(|entry| {
+                    serde_json::to_vec(&entry.response).map(|bytes| bytes.len()).unwrap_or(0)
+                })
+                .sum();
+            (guard.len(), bytes)
+        })
+        .unwrap_or((0, 0));
+    CacheStats {
+        entries,
+        hits: CACHE_HITS.load(Ordering::Relaxed),
+        misses: CACHE_MISSES.load(Ordering::Relaxed),
+        evictions: CACHE_EVICTIONS.load(Ordering::Relaxed),
+        approx_bytes,
+    }
 }
 
 fn try_read_cached_response(cache_key: &str) -> Option<ScreepsResponse> {
     let cache = response_cache();
     let mut guard = cache.lock().ok()?;
     let now = Instant::now();
-    guard.retain(|_, entry| entry.expires_at > now);
-    guard.get(cache_key).map(|entry| entry.response.clone())
+    prune_cache_entries(&mut guard, now);
+    let Some(entry) = guard.get(cache_key).filter(|entry| entry.expires_at > now) else {
+        CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
+        return None;
+    };
+    CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+    let mut response = entry.response.clone();
+    response.duration_ms = None;
+    response.from_cache = true;
+    response.raw_bytes_len = 0;
+    Some(response)
 }
 
-fn write_cached_response(cache_key: String, response: &ScreepsResponse, ttl: Duration) {
+fn try_read_stale_cached_response(cache_key: &str) -> Option<(ScreepsResponse, String)> {
+    let cache = response_cache();
+    let guard = cache.lock().ok()?;
+    let entry = guard.get(cache_key)?;
+    Some((entry.response.clone(), entry.etag.clone()?))
+}
+
+fn write_cached_response(
+    cache_key: String,
+    base_url: &str,
+    endpoint: &str,
+    response: &ScreepsResponse,
+    etag: Option<String>,
+    ttl: Duration,
+) {
     if !response.ok || ttl.is_zero() {
         return;
     }
@@ -137,42 +590,516 @@ fn write_cached_response(cache_key: String, response: &ScreepsResponse, ttl: Dur
     };
 
     let now = Instant::now();
-    guard.retain(|_, entry| entry.expires_at > now);
+    prune_cache_entries(&mut guard, now);
 
     if guard.len() >= RESPONSE_CACHE_MAX_ENTRIES {
         if let Some(oldest_key) =
             guard.iter().min_by_key(|(_, entry)| entry.expires_at).map(|(key, _)| key.clone())
         {
             guard.remove(&oldest_key);
+            CACHE_EVICTIONS.fetch_add(1, Ordering::Relaxed);
         }
     }
 
     guard.insert(
         cache_key,
-        ResponseCacheEntry { response: response.clone(), expires_at: now + ttl },
+        ResponseCacheEntry {
+            base_url: base_url.to_string(),
+            endpoint: endpoint.to_string(),
+            response: response.clone(),
+            etag,
+            expires_at: now + ttl,
+        },
     );
 }
 
+// Entries are keyed by the prefixed base URL, so a bare server URL also matches its proxied paths.
+fn cached_base_url_matches(entry_base_url: &str, base_url: &str) -> bool {
+    let entry_base_url = entry_base_url.to_ascii_lowercase();
+    let base_url = base_url.to_ascii_lowercase();
+    entry_base_url == base_url || entry_base_url.starts_with(&format!("{}/", base_url))
+}
+
+pub(crate) fn clear_response_cache(base_url: Option<&str>, endpoint: Option<&str>) -> usize {
+    let base_url = base_url.map(normalize_base_url);
+    let endpoint =
+        endpoint.map(|value| value.trim().to_ascii_lowercase()).filter(|value| !value.is_empty());
+
+    let cache = response_cache();
+    let Ok(mut guard) = cache.lock() else {
+        return 0;
+    };
+
+    let before = guard.len();
+    guard.retain(|_, entry| {
+        let base_url_matches =
+            base_url.as_deref().is_none_or(|value| cached_base_url_matches(&entry.base_url, value));
+        let endpoint_matches = endpoint
+            .as_deref()
+            .is_none_or(|value| entry.endpoint.to_ascii_lowercase().contains(value));
+        !(base_url_matches && endpoint_matches)
+    });
+    before - guard.len()
+}
+
+// Patches matching cached GET payloads in place (e.g. appending a just-sent message) rather than
+// throwing them away; `update` sees each entry's query pairs and returns whether it changed.
+pub(crate) fn update_cached_responses(
+    base_url: &str,
+    endpoint: &str,
+    mut update: impl FnMut(&HashMap<String, String>, &mut Value) -> bool,
+) -> usize {
+    let base_url = normalize_base_url(base_url);
+    let Ok(mut guard) = response_cache().lock() else {
+        return 0;
+    };
+    let mut updated = 0;
+    for entry in guard.values_mut() {
+        if !cached_base_url_matches(&entry.base_url, &base_url)
+            || !entry.endpoint.eq_ignore_ascii_case(endpoint)
+        {
+            continue;
+        }
+        let query = reqwest::Url::parse(&entry.response.url)
+            .map(|url| url.query_pairs().into_owned().collect::<HashMap<String, String>>())
+            .unwrap_or_default();
+        if update(&query, &mut entry.response.data) {
+            // The server's ETag no longer describes the patched body.
+            entry.etag = None;
+            updated += 1;
+        }
+    }
+    updated
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 502..=504)
+}
+
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request() || error.is_body()
+}
+
+fn next_jitter_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0);
+    let mut seed = JITTER_STATE.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed) ^ nanos;
+    seed ^= seed << 13;
+    seed ^= seed >> 7;
+    seed ^= seed << 17;
+    seed
+}
+
+fn retry_delay(base_delay_ms: u64, retry_index: u32) -> Duration {
+    let ceiling =
+        base_delay_ms.saturating_mul(1_u64 << retry_index.min(16)).min(MAX_RETRY_DELAY_MS);
+    if ceiling == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(next_jitter_seed() % (ceiling + 1))
+}
+
+struct RawHttpResponse {
+    status: u16,
+    url: String,
+    headers: HeaderMap,
+    bytes: Vec<u8>,
+    raw_bytes_len: usize,
+}
+
+impl RawHttpResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+async fn send_once(req: RequestBuilder) -> Result<RawHttpResponse, (String, bool)> {
+    let mut response = req
+        .send()
+        .await
+        .map_err(|error| (format!("request failed: {}", error), is_retryable_error(&error)))?;
+
+    let status = response.status().as_u16();
+    let url = response.url().to_string();
+    let headers = response.headers().clone();
+
+    // Stream the body so a runaway endpoint is cut off instead of being buffered whole.
+    let limit = MAX_RESPONSE_BYTES.load(Ordering::Relaxed);
+    let too_large = || (format!("response body exceeds the {} byte limit", limit), false);
+    if response.content_length().is_some_and(|length| length > limit) {
+        return Err(too_large());
+    }
+    let mut raw_bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|error| {
+        (format!("failed to read response body: {}", error), is_retryable_error(&error))
+    })? {
+        if (raw_bytes.len() + chunk.len()) as u64 > limit {
+            return Err(too_large());
+        }
+        raw_bytes.extend_from_slice(&chunk);
+    }
+    let raw_bytes_len = raw_bytes.len();
+    let content_encoding = headers.get(CONTENT_ENCODING).and_then(|value| value.to_str().ok());
+    let bytes =
+        decode_response_body(content_encoding, raw_bytes, limit).map_err(|error| (error, false))?;
+
+    Ok(RawHttpResponse { status, url, headers, bytes, raw_bytes_len })
+}
+
+fn decode_response_body(
+    content_encoding: Option<&str>,
+    bytes: Vec<u8>,
+    limit: u64,
+) -> Result<Vec<u8>, String> {
+    let Some(encoding) = content_encoding.map(|value| value.trim().to_ascii_lowercase()) else {
+        return Ok(bytes);
+    };
+    if bytes.is_empty() {
+        return Ok(bytes);
+    }
+
+    let mut decoded = Vec::new();
+    // Reading one byte past the limit is enough to tell a compression bomb from a full body.
+    let result = match encoding.as_str() {
+        "gzip" | "x-gzip" => {
+            GzDecoder::new(bytes.as_slice()).take(limit + 1).read_to_end(&mut decoded)
+        }
+        "deflate" => ZlibDecoder::new(bytes.as_slice()).take(limit + 1).read_to_end(&mut decoded),
+        "br" => brotli::Decompressor::new(bytes.as_slice(), 4_096)
+            .take(limit + 1)
+            .read_to_end(&mut decoded),
+        _ => return Ok(bytes),
+    };
+    result.map_err(|error| format!("failed to decode {} response body: {}", encoding, error))?;
+    if decoded.len() as u64 > limit {
+        return Err(format!("decoded response body exceeds the {} byte limit", limit));
+    }
+    Ok(decoded)
+}
+
+pub(crate) fn decode_gz_text(text: &str) -> Option<String> {
+    let encoded = text.trim().strip_prefix("gz:")?;
+    let compressed = BASE64_STANDARD.decode(encoded.trim()).ok()?;
+    let mut decoded = String::new();
+    if GzDecoder::new(compressed.as_slice()).read_to_string(&mut decoded).is_ok() {
+        return Some(decoded);
+    }
+    decoded.clear();
+    ZlibDecoder::new(compressed.as_slice()).read_to_string(&mut decoded).ok()?;
+    Some(decoded)
+}
+
+fn decode_gz_value(text: &str) -> Option<Value> {
+    if !text.trim_start().starts_with("gz:") {
+        return None;
+    }
+    let decoded = decode_gz_text(text)?;
+    Some(serde_json::from_str::<Value>(&decoded).unwrap_or(Value::String(decoded)))
+}
+
 pub(crate) fn request_url(request: &ScreepsRequest) -> String {
-    let base_url = normalize_base_url(&request.base_url);
+    let base_url = request_base_url(request);
     let endpoint = normalize_endpoint(&request.endpoint);
     format!("{}{}", base_url, endpoint)
 }
 
+// Tokens can end up in query strings or reqwest error text; never echo them back to the UI.
+pub(crate) fn redact_secrets(text: &str, token: Option<&str>) -> String {
+    match token.map(str::trim).filter(|token| token.len() >= MIN_REDACTED_SECRET_LEN) {
+        Some(token) => text.replace(token, "***"),
+        None => text.to_string(),
+    }
+}
+
 pub(crate) fn error_response(request: &ScreepsRequest, error: String) -> ScreepsResponse {
+    let token = request.token.as_deref();
     ScreepsResponse {
         status: 0,
         ok: false,
-        data: json!({ "error": error }),
-        url: request_url(request),
+        data: json!({ "error": redact_secrets(&error, token) }),
+        url: redact_secrets(&request_url(request), token),
+        duration_ms: None,
+        from_cache: false,
+        raw_bytes_len: 0,
+        token: None,
+        attempts: None,
     }
 }
 
+type InflightResult = Result<ScreepsResponse, String>;
+
+enum InflightSlot {
+    Leader(InflightLeader),
+    Follower(broadcast::Receiver<InflightResult>),
+}
+
+struct InflightLeader {
+    key: String,
+    finished: bool,
+}
+
+impl InflightLeader {
+    fn finish(mut self, result: &InflightResult) {
+        self.finished = true;
+        if let Ok(mut guard) = inflight_requests().lock() {
+            if let Some(sender) = guard.remove(&self.key) {
+                let _ = sender.send(result.clone());
+            }
+        }
+    }
+}
+
+impl Drop for InflightLeader {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        if let Ok(mut guard) = inflight_requests().lock() {
+            guard.remove(&self.key);
+        }
+    }
+}
+
+fn inflight_requests() -> &'static Mutex<HashMap<String, broadcast::Sender<InflightResult>>> {
+    INFLIGHT_REQUESTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn idempotency_key_for(request: &ScreepsRequest) -> Option<String> {
+    let method_name = request.method.as_deref().unwrap_or("GET").trim();
+    if method_name.eq_ignore_ascii_case("GET") || request.dry_run.unwrap_or(false) {
+        return None;
+    }
+    let key = request.idempotency_key.as_deref().map(str::trim).filter(|key| !key.is_empty())?;
+    Some(format!("idempotent|{}|{}", request_base_url(request), key))
+}
+
+fn circuit_breakers() -> &'static Mutex<HashMap<String, CircuitBreaker>> {
+    CIRCUIT_BREAKERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Once the cooldown lapses a single probe is let through; re-arming `opened_at` keeps other
+// callers out until it resolves, and a dropped probe simply re-opens the window next cooldown.
+fn check_circuit(key: &str, endpoint: &str) -> Result<(), String> {
+    let Ok(mut guard) = circuit_breakers().lock() else {
+        return Ok(());
+    };
+    let Some(breaker) = guard.get_mut(key) else {
+        return Ok(());
+    };
+    let Some(opened_at) = breaker.opened_at else {
+        return Ok(());
+    };
+    let cooldown = Duration::from_secs(CIRCUIT_COOLDOWN_SECS);
+    let elapsed = opened_at.elapsed();
+    if elapsed < cooldown {
+        return Err(format!(
+            "circuit open for {} after {} consecutive failures: retry in {}ms",
+            endpoint,
+            breaker.consecutive_failures,
+            (cooldown - elapsed).as_millis()
+        ));
+    }
+    breaker.opened_at = Some(Instant::now());
+    breaker.probing = true;
+    Ok(())
+}
+
+fn record_circuit_result(key: &str, success: bool) {
+    let Ok(mut guard) = circuit_breakers().lock() else {
+        return;
+    };
+    if success {
+        guard.remove(key);
+        return;
+    }
+    let breaker = guard.entry(key.to_string()).or_default();
+    breaker.consecutive_failures += 1;
+    if breaker.probing || breaker.consecutive_failures >= CIRCUIT_FAILURE_THRESHOLD {
+        breaker.opened_at = Some(Instant::now());
+        breaker.probing = false;
+    }
+}
+
+pub(crate) fn circuit_status() -> HashMap<String, CircuitStatus> {
+    let Ok(guard) = circuit_breakers().lock() else {
+        return HashMap::new();
+    };
+    let cooldown = Duration::from_secs(CIRCUIT_COOLDOWN_SECS);
+    guard
+        .iter()
+        .map(|(key, breaker)| {
+            let remaining =
+                breaker.opened_at.map(|opened_at| cooldown.saturating_sub(opened_at.elapsed()));
+            let state = match remaining {
+                None => CircuitState::Closed,
+                Some(_) if breaker.probing => CircuitState::HalfOpen,
+                Some(remaining) if remaining.is_zero() => CircuitState::HalfOpen,
+                Some(_) => CircuitState::Open,
+            };
+            let status = CircuitStatus {
+                state,
+                consecutive_failures: breaker.consecutive_failures,
+                retry_in_ms: remaining
+                    .filter(|remaining| !remaining.is_zero() && state == CircuitState::Open)
+                    .map(|remaining| remaining.as_millis() as u64),
+            };
+            (key.clone(), status)
+        })
+        .collect()
+}
+
+fn idempotent_responses() -> &'static Mutex<HashMap<String, (Instant, ScreepsResponse)>> {
+    IDEMPOTENT_RESPONSES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn recent_idempotent_response(key: &str) -> Option<ScreepsResponse> {
+    let guard = idempotent_responses().lock().ok()?;
+    let (completed_at, response) = guard.get(key)?;
+    (completed_at.elapsed() < Duration::from_secs(IDEMPOTENCY_WINDOW_SECS))
+        .then(|| response.clone())
+}
+
+fn remember_idempotent_response(key: String, response: &ScreepsResponse) {
+    let Ok(mut guard) = idempotent_responses().lock() else {
+        return;
+    };
+    let window = Duration::from_secs(IDEMPOTENCY_WINDOW_SECS);
+    guard.retain(|_, (completed_at, _)| completed_at.elapsed() < window);
+    guard.insert(key, (Instant::now(), response.clone()));
+}
+
+fn inflight_key_for(request: &ScreepsRequest) -> Option<String> {
+    // Concurrent duplicates of a keyed write wait for the first send instead of racing it.
+    if let Some(key) = idempotency_key_for(request) {
+        return Some(key);
+    }
+    let method_name = request.method.as_deref().unwrap_or("GET").trim();
+    if !method_name.eq_ignore_ascii_case("GET") || request.dry_run.unwrap_or(false) {
+        return None;
+    }
+    let base_url = request_base_url(request);
+    let endpoint = normalize_endpoint(&request.endpoint);
+    let query_pairs = request.query.as_ref().map(build_query_pairs).unwrap_or_default();
+    Some(build_response_cache_key(request, &base_url, &endpoint, &query_pairs))
+}
+
+fn join_inflight_request(key: String) -> Option<InflightSlot> {
+    let mut guard = inflight_requests().lock().ok()?;
+    if let Some(sender) = guard.get(&key) {
+        return Some(InflightSlot::Follower(sender.subscribe()));
+    }
+    let (sender, _) = broadcast::channel(1);
+    guard.insert(key.clone(), sender);
+    Some(InflightSlot::Leader(InflightLeader { key, finished: false }))
+}
+
 pub(crate) async fn perform_screeps_request(
     client: &Client,
     request: ScreepsRequest,
 ) -> Result<ScreepsResponse, String> {
-    let base_url = normalize_base_url(&request.base_url);
+    let token = request.token.clone();
+    let log_entry = REQUEST_LOGGING.load(Ordering::Relaxed).then(|| {
+        (
+            request.method.as_deref().unwrap_or("GET").to_ascii_uppercase(),
+            redact_secrets(&normalize_endpoint(&request.endpoint), token.as_deref()),
+            Instant::now(),
+        )
+    });
+    // Only successful responses are replayed; errors and failed statuses (5xx, 429) are left for
+    // the caller to retry with the same key.
+    let idempotency_key = idempotency_key_for(&request);
+    if let Some(prior) = idempotency_key.as_deref().and_then(recent_idempotent_response) {
+        return Ok(prior);
+    }
+    let result = dispatch_screeps_request(client, request)
+        .await
+        .map_err(|error| redact_secrets(&error, token.as_deref()));
+    if let (Some(key), Ok(response)) = (idempotency_key, result.as_ref()) {
+        if response.ok {
+            remember_idempotent_response(key, response);
+        }
+    }
+    if let Some((method, endpoint, started_at)) = log_entry {
+        let (status, from_cache) =
+            result.as_ref().map_or((0, false), |response| (response.status, response.from_cache));
+        record_request_log(RequestLogEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+            method,
+            endpoint,
+            status,
+            duration_ms: started_at.elapsed().as_millis() as u64,
+            from_cache,
+        });
+    }
+    result
+}
+
+fn request_log() -> &'static Mutex<VecDeque<RequestLogEntry>> {
+    REQUEST_LOG.get_or_init(|| Mutex::new(VecDeque::with_capacity(REQUEST_LOG_CAPACITY)))
+}
+
+fn record_request_log(entry: RequestLogEntry) {
+    let Ok(mut guard) = request_log().lock() else {
+        return;
+    };
+    if guard.len() >= REQUEST_LOG_CAPACITY {
+        guard.pop_front();
+    }
+    guard.push_back(entry);
+}
+
+pub(crate) fn set_request_logging(enabled: bool) -> bool {
+    REQUEST_LOGGING.store(enabled, Ordering::Relaxed);
+    enabled
+}
+
+pub(crate) fn request_log_entries() -> Vec<RequestLogEntry> {
+    request_log().lock().map(|guard| guard.iter().cloned().collect()).unwrap_or_default()
+}
+
+pub(crate) fn clear_request_log() -> usize {
+    request_log()
+        .lock()
+        .map(|mut guard| {
+            let cleared = guard.len();
+            guard.clear();
+            cleared
+        })
+        .unwrap_or(0)
+}
+
+async fn dispatch_screeps_request(
+    client: &Client,
+    request: ScreepsRequest,
+) -> Result<ScreepsResponse, String> {
+    let Some(slot) = inflight_key_for(&request).and_then(join_inflight_request) else {
+        return execute_screeps_request(client, request).await;
+    };
+
+    match slot {
+        // Followers share the leader's bytes; counting them again would double the reported traffic.
+        InflightSlot::Follower(mut receiver) => match receiver.recv().await {
+            Ok(result) => result.map(|response| ScreepsResponse { raw_bytes_len: 0, ..response }),
+            Err(_) => execute_screeps_request(client, request).await,
+        },
+        InflightSlot::Leader(leader) => {
+            let result = execute_screeps_request(client, request).await;
+            leader.finish(&result);
+            result
+        }
+    }
+}
+
+async fn execute_screeps_request(
+    client: &Client,
+    request: ScreepsRequest,
+) -> Result<ScreepsResponse, String> {
+    let base_url = request_base_url(&request);
     let endpoint = normalize_endpoint(&request.endpoint);
     let url = format!("{}{}", base_url, endpoint);
 
@@ -182,65 +1109,586 @@ pub(crate) async fn perform_screeps_request(
     let is_get_method = method == Method::GET;
 
     let query_pairs = request.query.as_ref().map(build_query_pairs).unwrap_or_default();
+    let custom_headers = build_custom_headers(&request)?;
+    if request.dry_run.unwrap_or(false) {
+        return Ok(dry_run_response(&request, &method, url, &query_pairs, &custom_headers));
+    }
     let cache_key = if is_get_method {
         Some(build_response_cache_key(&request, &base_url, &endpoint, &query_pairs))
     } else {
         None
     };
 
+    let disk_ttl_ms = disk_cache_ttl_ms(&method_name, &endpoint);
+    let disk_key = disk_ttl_ms.map(|_| {
+        disk_cache_key(&method_name, &base_url, &endpoint, &query_pairs, request.body.as_ref())
+    });
+
     if let Some(cache_key_value) = cache_key.as_deref() {
         if let Some(cached_response) = try_read_cached_response(cache_key_value) {
             return Ok(cached_response);
         }
     }
+    if let (Some(disk_key_value), Some(ttl_ms)) = (disk_key.as_deref(), disk_ttl_ms) {
+        if let Some(disk_response) = read_disk_cache(disk_key_value, ttl_ms) {
+            if let Some(cache_key_value) = cache_key.as_deref() {
+                write_cached_response(
+                    cache_key_value.to_string(),
+                    &base_url,
+                    &endpoint,
+                    &disk_response,
+                    None,
+                    cache_ttl_for_endpoint(&endpoint),
+                );
+            }
+            return Ok(ScreepsResponse { from_cache: true, raw_bytes_len: 0, ..disk_response });
+        }
+    }
+    let revalidation = cache_key.as_deref().and_then(try_read_stale_cached_response);
+    let circuit_key = format!("{}{}", base_url, endpoint);
+    check_circuit(&circuit_key, &endpoint)?;
 
-    let mut req = client.request(method, &url).header("Accept", "application/json");
+    let token = request.token.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    let username = request.username.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    let build_request = || {
+        let mut req = client.request(method.clone(), &url).header("Accept", "application/json");
 
-    if !query_pairs.is_empty() {
-        req = req.query(&query_pairs);
-    }
+        if !query_pairs.is_empty() {
+            req = req.query(&query_pairs);
+        }
 
-    if let Some(token) = request.token.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
-        req = req.header("X-Token", token);
-    }
+        if let Some(token) = token {
+            req = req.header("X-Token", token);
+        }
 
-    if let Some(username) =
-        request.username.as_deref().map(str::trim).filter(|value| !value.is_empty())
-    {
-        req = req.header("X-Username", username);
-    }
+        if let Some(username) = username {
+            req = req.header("X-Username", username);
+        }
 
-    if !is_get_method {
-        if let Some(body) = request.body.as_ref() {
-            req = req.json(body);
+        if let Some((_, etag)) = revalidation.as_ref() {
+            req = req.header("If-None-Match", etag.as_str());
         }
-    }
 
-    let response = req.send().await.map_err(|error| format!("request failed: {}", error))?;
+        if !custom_headers.is_empty() {
+            req = req.headers(custom_headers.clone());
+        }
 
-    let status = response.status().as_u16();
-    let final_url = response.url().to_string();
+        if !is_get_method {
+            if let Some(body) = request.body.as_ref() {
+                req = req.json(body);
+            }
+        }
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|error| format!("failed to read response body: {}", error))?;
+        req
+    };
+
+    let max_retries = if request.retryable.unwrap_or(is_get_method) {
+        request.max_retries.unwrap_or(DEFAULT_MAX_RETRIES).min(MAX_RETRIES_LIMIT)
+    } else {
+        0
+    };
+    let retry_base_delay_ms = request.retry_base_delay_ms.unwrap_or(DEFAULT_RETRY_BASE_DELAY_MS);
+    let mut attempts = 0;
+    let started_at = Instant::now();
+
+    let raw = loop {
+        attempts += 1;
+        let can_retry = attempts <= max_retries;
+        acquire_request_slot(&base_url).await?;
+        match send_once(build_request()).await {
+            Ok(raw) if raw.status == 429 => {
+                record_rate_limited(&base_url, raw.header("Retry-After"));
+                break raw;
+            }
+            Ok(raw) if can_retry && is_retryable_status(raw.status) => {}
+            Ok(raw) => break raw,
+            Err((_, true)) if can_retry => {}
+            Err((error, _)) => {
+                record_circuit_result(&circuit_key, false);
+                return Err(format!("{} (after {} attempt(s))", error, attempts));
+            }
+        }
+        tokio::time::sleep(retry_delay(retry_base_delay_ms, attempts - 1)).await;
+    };
+    // Rate limiting and client errors say nothing about endpoint health; only 5xx trips it.
+    if raw.status != 429 {
+        record_circuit_result(&circuit_key, raw.status < 500);
+    }
+
+    let duration_ms = started_at.elapsed().as_millis() as u64;
+    let ttl = cache_ttl_for_endpoint(&endpoint);
+    if raw.status == 304 {
+        if let (Some(cache_key_value), Some((cached_response, etag))) =
+            (cache_key.as_ref(), revalidation)
+        {
+            write_cached_response(
+                cache_key_value.clone(),
+                &base_url,
+                &endpoint,
+                &cached_response,
+                Some(etag),
+                ttl,
+            );
+            return Ok(ScreepsResponse {
+                duration_ms: Some(duration_ms),
+                from_cache: true,
+                raw_bytes_len: 0,
+                ..cached_response
+            });
+        }
+    }
 
+    let etag = raw.header("ETag").map(str::to_string);
+    let refreshed_token = raw.header("X-Token").map(str::to_string);
+    emit_token_refreshed(&base_url, token, refreshed_token.as_deref());
+    let RawHttpResponse { status, url: final_url, bytes, raw_bytes_len, .. } = raw;
     let data = if bytes.is_empty() {
         json!({})
     } else {
-        serde_json::from_slice::<Value>(&bytes).unwrap_or_else(|_| {
-            let text = String::from_utf8_lossy(&bytes).to_string();
-            json!({ "text": text })
-        })
+        match serde_json::from_slice::<Value>(&bytes) {
+            Ok(Value::String(text)) if text.starts_with("gz:") => {
+                decode_gz_value(&text).unwrap_or(Value::String(text))
+            }
+            Ok(mut value) => {
+                if let Some(decoded) =
+                    value.get("data").and_then(Value::as_str).and_then(decode_gz_value)
+                {
+                    value["data"] = decoded;
+                }
+                value
+            }
+            Err(_) => {
+                let text = String::from_utf8_lossy(&bytes).to_string();
+                decode_gz_value(&text).unwrap_or_else(|| json!({ "text": text }))
+            }
+        }
     };
 
-    let response =
-        ScreepsResponse { status, ok: (200..300).contains(&status), data, url: final_url };
+    let response = ScreepsResponse {
+        status,
+        ok: (200..300).contains(&status),
+        data,
+        url: final_url,
+        duration_ms: Some(duration_ms),
+        from_cache: false,
+        raw_bytes_len,
+        token: refreshed_token,
+        attempts: (attempts > 1 && is_retryable_status(status)).then_some(attempts),
+    };
 
     if let Some(cache_key_value) = cache_key {
-        write_cached_response(cache_key_value, &response, cache_ttl_for_endpoint(&endpoint));
+        write_cached_response(cache_key_value, &base_url, &endpoint, &response, etag, ttl);
+    }
+    if let Some(disk_key) = disk_key {
+        write_disk_cache(&disk_key, &response);
     }
 
     Ok(response)
 }
+
+// Offset/limit pagination; stops on a short page, the page cap or cancellation.
+pub(crate) async fn paginate(
+    client: &Client,
+    request: ScreepsRequest,
+    items_key: &str,
+    limit: usize,
+    max_pages: usize,
+    cancelled: &AtomicBool,
+) -> Result<Vec<Value>, String> {
+    let limit = limit.max(1);
+    let mut query = request.query.clone().unwrap_or_default();
+    let start_offset = query.get("offset").and_then(Value::as_u64).unwrap_or(0) as usize;
+    let mut items = Vec::new();
+
+    for page in 0..max_pages.clamp(1, MAX_PAGINATE_PAGES) {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        query.insert("offset".to_string(), json!(start_offset + page * limit));
+        query.insert("limit".to_string(), json!(limit));
+        let response = perform_screeps_request(
+            client,
+            ScreepsRequest { query: Some(query.clone()), ..request.clone() },
+        )
+        .await?;
+
+        if !response.ok {
+            return Err(format!("{} request failed: HTTP {}", request.endpoint, response.status));
+        }
+        if let Some(error) = response.data.get("error").and_then(Value::as_str) {
+            return Err(error.trim().to_string());
+        }
+        let page_items =
+            response.data.get(items_key).and_then(Value::as_array).cloned().unwrap_or_default();
+        let page_len = page_items.len();
+        items.extend(page_items);
+        if page_len < limit {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+// Auth headers are only attached for URLs on the configured server, never for third-party hosts.
+pub(crate) async fn fetch_binary(
+    client: &Client,
+    base_url: &str,
+    url: &str,
+    token: Option<&str>,
+    username: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let base_url = normalize_base_url(base_url);
+    let mut req = client.get(url);
+    if url.starts_with(&format!("{}/", base_url)) {
+        acquire_request_slot(&base_url).await?;
+        if let Some(token) = token.map(str::trim).filter(|value| !value.is_empty()) {
+            req = req.header("X-Token", token);
+        }
+        if let Some(username) = username.map(str::trim).filter(|value| !value.is_empty()) {
+            req = req.header("X-Username", username);
+        }
+    }
+
+    let raw = send_once(req).await.map_err(|(error, _)| error)?;
+    if !(200..300).contains(&raw.status) {
+        return Err(format!("binary request failed: HTTP {}", raw.status));
+    }
+    Ok(raw.bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const TOKEN: &str = "5f1c9e2a-secret-token";
+
+    fn request_with_token(token: &str) -> ScreepsRequest {
+        ScreepsRequest {
+            base_url: "https://screeps.com".to_string(),
+            endpoint: format!("/api/user/memory?_token={}", token),
+            token: Some(token.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn redact_secrets_masks_token_in_url() {
+        let url = format!("https://screeps.com/api/user/memory?_token={}&path=stats", TOKEN);
+        let redacted = redact_secrets(&url, Some(TOKEN));
+        assert!(!redacted.contains(TOKEN));
+        assert_eq!(redacted, "https://screeps.com/api/user/memory?_token=***&path=stats");
+    }
+
+    #[test]
+    fn redact_secrets_masks_token_in_reqwest_error_text() {
+        let error = format!(
+            "error sending request for url (https://screeps.com/api/auth/me?token={}): \
+             connection closed before message completed",
+            TOKEN
+        );
+        let redacted = redact_secrets(&error, Some(&format!("  {}  ", TOKEN)));
+        assert!(!redacted.contains(TOKEN));
+        assert!(redacted.contains("token=***"));
+    }
+
+    #[test]
+    fn redact_secrets_leaves_short_tokens_alone() {
+        let short = "a".repeat(MIN_REDACTED_SECRET_LEN - 1);
+        let text = format!("value {} stays", short);
+        assert_eq!(redact_secrets(&text, Some(&short)), text);
+        assert_eq!(redact_secrets(&text, None), text);
+    }
+
+    #[test]
+    fn error_response_never_echoes_token() {
+        let request = request_with_token(TOKEN);
+        let error = format!("request to {} failed", request_url(&request));
+        let response = error_response(&request, error);
+        let error_text = response.data.get("error").and_then(Value::as_str).unwrap_or_default();
+        assert!(!error_text.is_empty());
+        assert!(!error_text.contains(TOKEN));
+        assert!(!response.url.contains(TOKEN));
+        assert!(response.token.is_none());
+    }
+
+    #[tokio::test]
+    async fn transport_errors_never_echo_token() {
+        // Bind and drop a listener so the port is known to refuse connections.
+        let port = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .and_then(|listener| listener.local_addr())
+            .map(|addr| addr.port())
+            .expect("bind test port");
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+        let request = ScreepsRequest {
+            base_url: format!("http://127.0.0.1:{}", port),
+            endpoint: "/api/auth/me".to_string(),
+            token: Some(TOKEN.to_string()),
+            query: Some(HashMap::from([("token".to_string(), json!(TOKEN))])),
+            max_retries: Some(0),
+            ..Default::default()
+        };
+        let error = perform_screeps_request(&client, request).await.expect_err("request fails");
+        assert!(!error.contains(TOKEN), "token leaked: {}", error);
+    }
+
+    async fn serve_canned(responses: Vec<String>) -> (String, Arc<Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind mock");
+        let base_url = format!("http://{}", listener.local_addr().expect("mock addr"));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let task_seen = seen.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut head = Vec::new();
+                let mut buffer = [0_u8; 1024];
+                while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => head.extend_from_slice(&buffer[..read]),
+                    }
+                }
+                task_seen.lock().unwrap().push(String::from_utf8_lossy(&head).to_lowercase());
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+        (base_url, seen)
+    }
+
+    fn canned_ok(etag: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nETag: {}\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            etag,
+            body.len(),
+            body
+        )
+    }
+
+    fn canned_not_modified(etag: &str) -> String {
+        format!(
+            "HTTP/1.1 304 Not Modified\r\nETag: {}\r\nContent-Length: 0\r\n\
+             Connection: close\r\n\r\n",
+            etag
+        )
+    }
+
+    fn canned_status(status_line: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            status_line,
+            body.len(),
+            body
+        )
+    }
+
+    fn cached_entry(base_url: &str, endpoint: &str) -> Option<ResponseCacheEntry> {
+        response_cache()
+            .lock()
+            .unwrap()
+            .values()
+            .find(|entry| entry.base_url == base_url && entry.endpoint == endpoint)
+            .cloned()
+    }
+
+    // Forces the next request to revalidate instead of being served from the fresh cache.
+    fn expire_cached(base_url: &str, endpoint: &str) {
+        let now = Instant::now();
+        for entry in response_cache().lock().unwrap().values_mut() {
+            if entry.base_url == base_url && entry.endpoint == endpoint {
+                entry.expires_at = now;
+            }
+        }
+    }
+
+    fn revalidation_request(base_url: &str, endpoint: &str) -> ScreepsRequest {
+        ScreepsRequest {
+            base_url: base_url.to_string(),
+            endpoint: endpoint.to_string(),
+            max_retries: Some(0),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn not_modified_returns_stale_body_from_cache() {
+        let endpoint = "/api/test/revalidate-304";
+        let (base_url, seen) = serve_canned(vec![
+            canned_ok("\"v1\"", r#"{"ok":1,"value":1}"#),
+            canned_not_modified("\"v1\""),
+        ])
+        .await;
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+
+        let first = perform_screeps_request(&client, revalidation_request(&base_url, endpoint))
+            .await
+            .expect("first request");
+        assert!(!first.from_cache);
+        assert!(first.raw_bytes_len > 0);
+        expire_cached(&base_url, endpoint);
+
+        let second = perform_screeps_request(&client, revalidation_request(&base_url, endpoint))
+            .await
+            .expect("revalidated request");
+        assert_eq!(second.status, 200);
+        assert!(second.from_cache);
+        assert_eq!(second.raw_bytes_len, 0);
+        assert_eq!(second.data["value"], json!(1));
+        assert!(seen.lock().unwrap()[1].contains("if-none-match: \"v1\""));
+    }
+
+    #[tokio::test]
+    async fn not_modified_keeps_etag_on_refreshed_entry() {
+        let endpoint = "/api/test/revalidate-etag";
+        let (base_url, _) = serve_canned(vec![
+            canned_ok("\"v1\"", r#"{"ok":1,"value":1}"#),
+            canned_not_modified("\"v1\""),
+        ])
+        .await;
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+
+        perform_screeps_request(&client, revalidation_request(&base_url, endpoint))
+            .await
+            .expect("first request");
+        expire_cached(&base_url, endpoint);
+        perform_screeps_request(&client, revalidation_request(&base_url, endpoint))
+            .await
+            .expect("revalidated request");
+
+        let entry = cached_entry(&base_url, endpoint).expect("entry refreshed");
+        assert_eq!(entry.etag.as_deref(), Some("\"v1\""));
+        assert!(entry.expires_at > Instant::now());
+        assert_eq!(entry.response.data["value"], json!(1));
+    }
+
+    #[tokio::test]
+    async fn modified_response_replaces_cached_entry() {
+        let endpoint = "/api/test/revalidate-200";
+        let (base_url, _) = serve_canned(vec![
+            canned_ok("\"v1\"", r#"{"ok":1,"value":1}"#),
+            canned_ok("\"v2\"", r#"{"ok":1,"value":2}"#),
+        ])
+        .await;
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+
+        perform_screeps_request(&client, revalidation_request(&base_url, endpoint))
+            .await
+            .expect("first request");
+        expire_cached(&base_url, endpoint);
+
+        let second = perform_screeps_request(&client, revalidation_request(&base_url, endpoint))
+            .await
+            .expect("second request");
+        assert!(!second.from_cache);
+        assert_eq!(second.data["value"], json!(2));
+
+        let entry = cached_entry(&base_url, endpoint).expect("entry replaced");
+        assert_eq!(entry.etag.as_deref(), Some("\"v2\""));
+        assert_eq!(entry.response.data["value"], json!(2));
+    }
+
+    #[tokio::test]
+    async fn dry_run_masks_custom_header_values() {
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+        let request = ScreepsRequest {
+            base_url: "https://screeps.com".to_string(),
+            endpoint: "/api/auth/me".to_string(),
+            token: Some(TOKEN.to_string()),
+            headers: Some(HashMap::from([
+                ("Authorization".to_string(), "Bearer proxy-secret".to_string()),
+                ("Cookie".to_string(), "session=abc123".to_string()),
+                ("CF-Access-Client-Secret".to_string(), "cf-secret".to_string()),
+                ("User-Agent".to_string(), "dashboard-test".to_string()),
+            ])),
+            dry_run: Some(true),
+            ..Default::default()
+        };
+        let response = perform_screeps_request(&client, request).await.expect("dry run");
+        let headers = &response.data["headers"];
+        assert_eq!(headers["authorization"], json!("***"));
+        assert_eq!(headers["cookie"], json!("***"));
+        assert_eq!(headers["cf-access-client-secret"], json!("***"));
+        assert_eq!(headers["user-agent"], json!("dashboard-test"));
+        assert_eq!(headers["X-Token"], json!("***"));
+    }
+
+    #[tokio::test]
+    async fn idempotent_retry_is_resent_after_server_error_and_replayed_after_success() {
+        let (base_url, seen) = serve_canned(vec![
+            canned_status("503 Service Unavailable", r#"{"error":"unavailable"}"#),
+            canned_ok("\"v1\"", r#"{"ok":1}"#),
+        ])
+        .await;
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+        let request = || ScreepsRequest {
+            base_url: base_url.clone(),
+            endpoint: "/api/user/console".to_string(),
+            method: Some("POST".to_string()),
+            body: Some(json!({ "expression": "Game.time" })),
+            idempotency_key: Some("console-1".to_string()),
+            ..Default::default()
+        };
+
+        let failed = perform_screeps_request(&client, request()).await.expect("first send");
+        assert_eq!(failed.status, 503);
+        let sent = perform_screeps_request(&client, request()).await.expect("retried send");
+        assert_eq!(sent.status, 200);
+        assert_eq!(seen.lock().unwrap().len(), 2);
+
+        let replayed = perform_screeps_request(&client, request()).await.expect("replayed send");
+        assert_eq!(replayed.status, 200);
+        assert_eq!(replayed.data["ok"], json!(1));
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_report_attempt_count() {
+        let unavailable = canned_status("503 Service Unavailable", r#"{"error":"unavailable"}"#);
+        let (base_url, seen) = serve_canned(vec![unavailable; 3]).await;
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+        let request = ScreepsRequest {
+            base_url,
+            endpoint: "/api/test/retry-503".to_string(),
+            max_retries: Some(2),
+            retry_base_delay_ms: Some(1),
+            ..Default::default()
+        };
+
+        let response = perform_screeps_request(&client, request).await.expect("final response");
+        assert_eq!(response.status, 503);
+        assert_eq!(response.attempts, Some(3));
+        assert_eq!(seen.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn update_cached_responses_matches_prefixed_entries() {
+        let endpoint = "/api/test/prefixed-thread";
+        let (base_url, _) =
+            serve_canned(vec![canned_ok("\"v1\"", r#"{"messages":[{"_id":"a"}]}"#)]).await;
+        set_path_prefix(&base_url, "/screeps").expect("set prefix");
+        let client = build_http_client(DEFAULT_CLIENT_TIMEOUTS).expect("build client");
+        perform_screeps_request(&client, revalidation_request(&base_url, endpoint))
+            .await
+            .expect("cached request");
+
+        let updated = update_cached_responses(&base_url, endpoint, |_, data| {
+            data["messages"]
+                .as_array_mut()
+                .map(|messages| messages.push(json!({ "_id": "b" })))
+                .is_some()
+        });
+        assert_eq!(updated, 1);
+
+        let entry = cached_entry(&format!("{}/screeps", base_url), endpoint).expect("entry");
+        assert_eq!(entry.response.data["messages"][1]["_id"], json!("b"));
+        assert!(entry.etag.is_none());
+    }
+}
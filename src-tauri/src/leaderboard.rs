@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest};
+
+const DEFAULT_LEADERBOARD_LIMIT: usize = 20;
+const MAX_LEADERBOARD_LIMIT: usize = 20;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsLeaderboardRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub mode: Option<String>,
+    pub season: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub find_username: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    pub rank: u64,
+    pub username: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsLeaderboardResponse {
+    pub mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub season: Option<String>,
+    pub entries: Vec<LeaderboardEntry>,
+    pub count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub found: Option<LeaderboardEntry>,
+}
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn value_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+fn normalize_mode(mode: Option<&str>) -> Result<String, String> {
+    let mode = mode.map(str::trim).filter(|value| !value.is_empty()).unwrap_or("world");
+    let mode = mode.to_ascii_lowercase();
+    if mode != "world" && mode != "power" {
+        return Err(format!("Unsupported leaderboard mode: {}", mode));
+    }
+    Ok(mode)
+}
+
+async fn leaderboard_get(
+    request: &ScreepsLeaderboardRequest,
+    endpoint: &str,
+    query: HashMap<String, Value>,
+) -> Result<Value, String> {
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url.clone(),
+            endpoint: endpoint.to_string(),
+            method: Some("GET".to_string()),
+            token: Some(request.token.clone()),
+            username: Some(request.username.clone()),
+            query: Some(query),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("{} request failed: HTTP {}", endpoint, response.status));
+    }
+    if let Some(error) = response.data.get("error").and_then(value_as_non_empty_string) {
+        return Err(error);
+    }
+    Ok(response.data)
+}
+
+async fn current_season(request: &ScreepsLeaderboardRequest) -> Option<String> {
+    let payload =
+        leaderboard_get(request, "/api/leaderboard/seasons", HashMap::new()).await.ok()?;
+    let seasons = payload.get("seasons")?.as_array()?;
+    seasons.iter().filter_map(|season| season.get("_id").and_then(value_as_non_empty_string)).max()
+}
+
+fn parse_leaderboard_entry(
+    item: &Value,
+    users: Option<&serde_json::Map<String, Value>>,
+    fallback_username: Option<&str>,
+) -> Option<LeaderboardEntry> {
+    let user_id = item.get("user").and_then(value_as_non_empty_string);
+    let username = user_id
+        .as_deref()
+        .and_then(|id| users?.get(id))
+        .and_then(|user| user.get("username"))
+        .and_then(value_as_non_empty_string)
+        .or_else(|| fallback_username.map(str::to_string))
+        .or(user_id)?;
+    let rank = item.get("rank").and_then(value_as_f64)?;
+    Some(LeaderboardEntry {
+        // The server reports zero-based ranks.
+        rank: rank.max(0.0) as u64 + 1,
+        username,
+        score: item.get("score").and_then(value_as_f64).unwrap_or(0.0),
+    })
+}
+
+#[tauri::command]
+pub async fn screeps_leaderboard(
+    request: ScreepsLeaderboardRequest,
+) -> Result<ScreepsLeaderboardResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let mode = normalize_mode(request.mode.as_deref())?;
+    let season = match request.season.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => Some(value.to_string()),
+        None => current_season(&request).await,
+    };
+    let limit = request.limit.unwrap_or(DEFAULT_LEADERBOARD_LIMIT).clamp(1, MAX_LEADERBOARD_LIMIT);
+
+    let mut query = HashMap::from([("mode".to_string(), Value::String(mode.clone()))]);
+    if let Some(season) = season.as_ref() {
+        query.insert("season".to_string(), Value::String(season.clone()));
+    }
+
+    let mut list_query = query.clone();
+    list_query.insert("limit".to_string(), json!(limit));
+    list_query.insert("offset".to_string(), json!(request.offset.unwrap_or(0)));
+    let list_payload = leaderboard_get(&request, "/api/leaderboard/list", list_query).await?;
+    let users = list_payload.get("users").and_then(Value::as_object);
+    let entries = list_payload
+        .get("list")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items.iter().filter_map(|item| parse_leaderboard_entry(item, users, None)).collect()
+        })
+        .unwrap_or_default();
+    let count = list_payload.get("count").and_then(value_as_f64).unwrap_or(0.0).max(0.0) as u64;
+
+    let find_username =
+        request.find_username.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    let found = match find_username {
+        Some(find_username) => {
+            let mut find_query = query;
+            find_query.insert("username".to_string(), Value::String(find_username.to_string()));
+            let payload = leaderboard_get(&request, "/api/leaderboard/find", find_query).await?;
+            let item = payload
+                .get("list")
+                .and_then(Value::as_array)
+                .and_then(|items| items.last())
+                .unwrap_or(&payload);
+            parse_leaderboard_entry(item, None, Some(find_username))
+        }
+        None => None,
+    };
+
+    Ok(ScreepsLeaderboardResponse { mode, season, entries, count, found })
+}
@@ -1,29 +1,179 @@
+mod activity;
+mod auth;
+mod avatars;
+mod code;
 mod console;
+mod disk_cache;
+mod game_time;
 mod http;
+mod leaderboard;
+mod map;
+mod market;
+mod memory;
 mod messages;
+mod rate_limit;
 mod requests;
+mod resources;
+mod room_diff;
+mod room_export;
+mod room_history;
 mod rooms;
+mod shards;
+mod users;
+mod ws;
 
-use crate::console::screeps_console_execute;
+use tauri::Manager;
+
+use crate::activity::{screeps_nukes, screeps_pvp_feed};
+use crate::auth::{
+    screeps_auth_check, screeps_auth_signin, screeps_auth_signin_2fa, screeps_me_stats,
+    screeps_world_status,
+};
+use crate::avatars::screeps_avatar_fetch;
+use crate::code::{screeps_code_branches, screeps_code_fetch, screeps_code_push};
+use crate::console::{
+    screeps_console_execute, screeps_console_execute_batch, screeps_console_history,
+    screeps_console_history_clear,
+};
+use crate::game_time::{screeps_game_time, screeps_ping, screeps_tick_rate};
+use crate::leaderboard::screeps_leaderboard;
+use crate::map::{
+    screeps_map_rooms, screeps_map_stats_fetch, screeps_room_minimap, screeps_room_status,
+    screeps_world_size,
+};
+use crate::market::{
+    screeps_market_cancel_order, screeps_market_depth, screeps_market_history,
+    screeps_market_my_orders, screeps_market_orders,
+};
+use crate::memory::{screeps_memory_get, screeps_memory_set, screeps_runtime_stats};
 use crate::messages::{
-    screeps_messages_fetch, screeps_messages_fetch_thread, screeps_messages_send,
+    screeps_conversation_clear, screeps_message_send, screeps_messages_delete,
+    screeps_messages_fetch, screeps_messages_fetch_thread, screeps_messages_mark_read,
+    screeps_messages_search, screeps_messages_send, screeps_messages_unread_count,
+};
+use crate::requests::{
+    screeps_cache_clear, screeps_cache_configure, screeps_cache_purge_disk, screeps_cache_stats,
+    screeps_cancel, screeps_circuit_status, screeps_rate_limit_status, screeps_request,
+    screeps_request_log, screeps_request_log_clear, screeps_request_many,
+    screeps_request_paginated, screeps_set_limits, screeps_set_logging, screeps_set_path_prefix,
+    screeps_set_timeouts, screeps_set_user_agent,
+};
+use crate::resources::screeps_resource_list;
+use crate::room_diff::{
+    screeps_room_baseline_clear, screeps_room_baseline_diff, screeps_room_baseline_save,
+    screeps_room_diff,
+};
+use crate::room_export::screeps_room_export;
+use crate::room_history::screeps_room_history_fetch;
+use crate::rooms::{
+    screeps_empire_resources, screeps_room_defense, screeps_room_detail_fetch,
+    screeps_rooms_detail_fetch_many,
+};
+use crate::shards::screeps_shards_list;
+use crate::users::{screeps_user_badge, screeps_user_find};
+use crate::ws::{
+    screeps_console_subscribe, screeps_console_unsubscribe, screeps_map_subscribe,
+    screeps_map_unsubscribe, screeps_room_subscribe, screeps_room_unsubscribe,
 };
-use crate::requests::{screeps_request, screeps_request_many};
-use crate::rooms::screeps_room_detail_fetch;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            crate::http::set_app_handle(app.handle().clone());
+            if let Ok(dir) = app.path().app_data_dir() {
+                crate::disk_cache::set_disk_cache_dir(dir.join("http-cache"));
+                crate::avatars::set_avatar_cache_dir(dir.join("avatars"));
+                crate::room_export::set_export_dir(dir.join("exports"));
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             screeps_request,
             screeps_request_many,
+            screeps_request_paginated,
+            screeps_cancel,
+            screeps_rate_limit_status,
+            screeps_circuit_status,
+            screeps_cache_clear,
+            screeps_cache_configure,
+            screeps_cache_purge_disk,
+            screeps_cache_stats,
+            screeps_set_user_agent,
+            screeps_set_timeouts,
+            screeps_set_limits,
+            screeps_set_logging,
+            screeps_request_log,
+            screeps_request_log_clear,
+            screeps_set_path_prefix,
+            screeps_auth_check,
+            screeps_auth_signin,
+            screeps_auth_signin_2fa,
+            screeps_me_stats,
+            screeps_world_status,
+            screeps_game_time,
+            screeps_tick_rate,
+            screeps_ping,
             screeps_console_execute,
+            screeps_console_execute_batch,
+            screeps_console_history,
+            screeps_console_history_clear,
+            screeps_console_subscribe,
+            screeps_console_unsubscribe,
             screeps_messages_fetch,
             screeps_messages_fetch_thread,
+            screeps_messages_mark_read,
+            screeps_messages_unread_count,
             screeps_messages_send,
-            screeps_room_detail_fetch
+            screeps_avatar_fetch,
+            screeps_message_send,
+            screeps_messages_delete,
+            screeps_conversation_clear,
+            screeps_messages_search,
+            screeps_room_detail_fetch,
+            screeps_rooms_detail_fetch_many,
+            screeps_empire_resources,
+            screeps_room_defense,
+            screeps_map_stats_fetch,
+            screeps_room_minimap,
+            screeps_room_status,
+            screeps_world_size,
+            screeps_map_rooms,
+            screeps_pvp_feed,
+            screeps_nukes,
+            screeps_shards_list,
+            screeps_market_orders,
+            screeps_market_my_orders,
+            screeps_market_cancel_order,
+            screeps_market_history,
+            screeps_market_depth,
+            screeps_resource_list,
+            screeps_leaderboard,
+            screeps_user_badge,
+            screeps_user_find,
+            screeps_memory_get,
+            screeps_memory_set,
+            screeps_runtime_stats,
+            screeps_code_fetch,
+            screeps_code_branches,
+            screeps_code_push,
+            screeps_room_subscribe,
+            screeps_room_unsubscribe,
+            screeps_map_subscribe,
+            screeps_map_unsubscribe,
+            screeps_room_baseline_save,
+            screeps_room_baseline_diff,
+            screeps_room_baseline_clear,
+            screeps_room_diff,
+            screeps_room_history_fetch,
+            screeps_room_export
         ])
+        .on_window_event(|_, event| {
+            if matches!(event, tauri::WindowEvent::Destroyed) {
+                crate::ws::close_all_socket_sessions();
+            }
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
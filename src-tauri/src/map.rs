@@ -0,0 +1,521 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest};
+use crate::rooms::{normalize_room_name, normalize_shard_for, room_name_to_xy, room_xy_to_name};
+
+const DEFAULT_MAP_STAT_NAME: &str = "owner0";
+const SECTOR_SIZE: i64 = 10;
+const MAP_STATS_CHUNK_SIZE: usize = 100;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMapStatsRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub shard: Option<String>,
+    pub rooms: Vec<String>,
+    pub stat_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomMinimapRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub shard: Option<String>,
+    pub rooms: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MinimapEntry {
+    pub room: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomStatusRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub room_name: String,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomStatusResponse {
+    pub room_name: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub novice_end: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub respawn_area: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsWorldSizeRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMapRoomsRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub shard: Option<String>,
+    pub rooms: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsWorldSizeResponse {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MapRoomCell {
+    pub room_name: String,
+    pub x: i64,
+    pub y: i64,
+    pub sector: String,
+    pub highway: bool,
+    pub center: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMapRoomsResponse {
+    pub width: u32,
+    pub height: u32,
+    pub rooms: Vec<MapRoomCell>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MapRoomSign {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MapStatEntry {
+    pub room_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller_level: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign: Option<MapRoomSign>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn value_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+fn value_as_epoch_millis(value: &Value) -> Option<u64> {
+    let raw = value_as_f64(value)?;
+    if !raw.is_finite() || raw <= 0.0 {
+        return None;
+    }
+    Some(raw as u64)
+}
+
+fn payload_error(payload: &Value) -> Option<String> {
+    payload.get("error").and_then(value_as_non_empty_string)
+}
+
+fn sector_index(coordinate: i64) -> i64 {
+    // Negative coordinates are shifted by one, so W0..W9 share a sector just like E0..E9.
+    let value = if coordinate < 0 { -coordinate - 1 } else { coordinate };
+    value % SECTOR_SIZE
+}
+
+fn sector_center(coordinate: i64) -> i64 {
+    let value = if coordinate < 0 { -coordinate - 1 } else { coordinate };
+    let center = value / SECTOR_SIZE * SECTOR_SIZE + SECTOR_SIZE / 2;
+    if coordinate < 0 {
+        -center - 1
+    } else {
+        center
+    }
+}
+
+fn map_room_cell(x: i64, y: i64) -> MapRoomCell {
+    let (sector_x, sector_y) = (sector_index(x), sector_index(y));
+    MapRoomCell {
+        room_name: room_xy_to_name(x, y),
+        x,
+        y,
+        sector: room_xy_to_name(sector_center(x), sector_center(y)),
+        highway: sector_x == 0 || sector_y == 0,
+        center: sector_x == SECTOR_SIZE / 2 && sector_y == SECTOR_SIZE / 2,
+    }
+}
+
+async fn fetch_world_size(
+    base_url: String,
+    token: String,
+    username: String,
+    shard: Option<String>,
+) -> Result<ScreepsWorldSizeResponse, String> {
+    if token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let mut query = HashMap::new();
+    if let Some(shard) = normalize_shard_for(&base_url, shard.as_deref()) {
+        query.insert("shard".to_string(), Value::String(shard));
+    }
+
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url,
+            endpoint: "/api/game/world-size".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(token),
+            username: Some(username),
+            query: Some(query),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("world size request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+
+    let dimension = |key: &str| {
+        response
+            .data
+            .get(key)
+            .and_then(value_as_f64)
+            .filter(|value| value.is_finite() && *value > 0.0)
+            .map(|value| value as u32)
+            .ok_or_else(|| format!("world size response did not include {}", key))
+    };
+    Ok(ScreepsWorldSizeResponse { width: dimension("width")?, height: dimension("height")? })
+}
+
+fn parse_map_stat_entry(
+    room_name: &str,
+    stat: &Value,
+    users: Option<&serde_json::Map<String, Value>>,
+) -> MapStatEntry {
+    let own = stat.get("own");
+    let owner_id = own.and_then(|value| value.get("user")).and_then(value_as_non_empty_string);
+    let owner = owner_id
+        .as_deref()
+        .and_then(|id| users?.get(id))
+        .and_then(|user| user.get("username"))
+        .and_then(value_as_non_empty_string);
+    let sign = stat.get("sign").and_then(|sign| {
+        let text = sign.get("text").and_then(value_as_non_empty_string)?;
+        let username = sign
+            .get("user")
+            .and_then(value_as_non_empty_string)
+            .and_then(|id| users?.get(&id))
+            .and_then(|user| user.get("username"))
+            .and_then(value_as_non_empty_string);
+        Some(MapRoomSign { username, text, time: sign.get("time").and_then(value_as_f64) })
+    });
+
+    MapStatEntry {
+        room_name: room_name.to_string(),
+        status: stat.get("status").and_then(value_as_non_empty_string),
+        owner,
+        owner_id,
+        controller_level: own.and_then(|value| value.get("level")).and_then(value_as_f64),
+        sign,
+        error: None,
+    }
+}
+
+fn map_stat_error_entry(room_name: &str, error: &str) -> MapStatEntry {
+    MapStatEntry {
+        room_name: room_name.to_string(),
+        status: None,
+        owner: None,
+        owner_id: None,
+        controller_level: None,
+        sign: None,
+        error: Some(error.to_string()),
+    }
+}
+
+async fn fetch_map_stats(
+    request: ScreepsMapStatsRequest,
+) -> Result<HashMap<String, MapStatEntry>, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let mut rooms = request
+        .rooms
+        .iter()
+        .map(|room| normalize_room_name(room))
+        .collect::<Result<Vec<String>, String>>()?;
+    rooms.sort();
+    rooms.dedup();
+    if rooms.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let stat_name = request
+        .stat_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_MAP_STAT_NAME)
+        .to_string();
+
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+
+    let client = shared_http_client()?;
+    let mut handles = Vec::new();
+    for chunk in rooms.chunks(MAP_STATS_CHUNK_SIZE) {
+        let chunk = chunk.to_vec();
+        let task_client = client.clone();
+        let task_request = ScreepsRequest {
+            base_url: request.base_url.clone(),
+            endpoint: "/api/game/map-stats".to_string(),
+            method: Some("POST".to_string()),
+            token: Some(request.token.clone()),
+            username: Some(request.username.clone()),
+            body: Some(json!({
+                "rooms": chunk,
+                "statName": stat_name,
+                "shard": shard,
+            })),
+            ..Default::default()
+        };
+        handles.push(tauri::async_runtime::spawn(async move {
+            let result = fetch_map_stats_chunk(&task_client, task_request).await;
+            (chunk, result)
+        }));
+    }
+
+    let mut merged = HashMap::with_capacity(rooms.len());
+    let mut last_error = None;
+    let mut succeeded = false;
+    for handle in handles {
+        let (chunk, result) =
+            handle.await.map_err(|error| format!("map stats task failed: {}", error))?;
+        match result {
+            Ok(stats) => {
+                succeeded = true;
+                merged.extend(stats);
+            }
+            // A failed chunk is reported per room so the other chunks' data is still usable.
+            Err(error) => {
+                for room in &chunk {
+                    merged.insert(room.clone(), map_stat_error_entry(room, &error));
+                }
+                last_error = Some(error);
+            }
+        }
+    }
+
+    match last_error {
+        Some(error) if !succeeded => Err(error),
+        _ => Ok(merged),
+    }
+}
+
+async fn fetch_map_stats_chunk(
+    client: &Client,
+    request: ScreepsRequest,
+) -> Result<HashMap<String, MapStatEntry>, String> {
+    let response = perform_screeps_request(client, request).await?;
+
+    if !response.ok {
+        return Err(format!("map stats request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+
+    let users = response.data.get("users").and_then(Value::as_object);
+    let Some(stats) = response.data.get("stats").and_then(Value::as_object) else {
+        return Err("map stats response did not include stats".to_string());
+    };
+    Ok(stats
+        .iter()
+        .map(|(room_name, stat)| (room_name.clone(), parse_map_stat_entry(room_name, stat, users)))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn screeps_map_stats_fetch(
+    request: ScreepsMapStatsRequest,
+) -> Result<HashMap<String, MapStatEntry>, String> {
+    fetch_map_stats(request).await
+}
+
+#[tauri::command]
+pub async fn screeps_room_minimap(
+    request: ScreepsRoomMinimapRequest,
+) -> Result<Vec<MinimapEntry>, String> {
+    let rooms = request
+        .rooms
+        .iter()
+        .map(|room| normalize_room_name(room))
+        .collect::<Result<Vec<String>, String>>()?;
+    let mut stats = fetch_map_stats(ScreepsMapStatsRequest {
+        base_url: request.base_url,
+        token: request.token,
+        username: request.username,
+        shard: request.shard,
+        rooms: rooms.clone(),
+        stat_name: None,
+    })
+    .await?;
+
+    // Every requested room gets an entry so a failed lookup never reads as an empty room.
+    Ok(rooms
+        .into_iter()
+        .map(|room| {
+            let entry = stats.remove(&room).unwrap_or_else(|| {
+                map_stat_error_entry(&room, "room missing from map stats response")
+            });
+            MinimapEntry {
+                room,
+                owner: entry.owner,
+                level: entry.controller_level,
+                status: entry.status,
+                sign: entry.sign.map(|sign| sign.text),
+                error: entry.error,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn screeps_room_status(
+    request: ScreepsRoomStatusRequest,
+) -> Result<ScreepsRoomStatusResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let room_name = normalize_room_name(&request.room_name)?;
+    let mut query = HashMap::from([("room".to_string(), Value::String(room_name.clone()))]);
+    if let Some(shard) = normalize_shard_for(&request.base_url, request.shard.as_deref()) {
+        query.insert("shard".to_string(), Value::String(shard));
+    }
+
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url,
+            endpoint: "/api/game/room-status".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(request.token),
+            username: Some(request.username),
+            query: Some(query),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("room status request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+
+    let room = response.data.get("room").unwrap_or(&Value::Null);
+    Ok(ScreepsRoomStatusResponse {
+        room_name,
+        status: room
+            .get("status")
+            .and_then(value_as_non_empty_string)
+            .unwrap_or_else(|| "normal".to_string()),
+        open_time: room.get("openTime").and_then(value_as_epoch_millis),
+        novice_end: room.get("novice").and_then(value_as_epoch_millis),
+        respawn_area: room.get("respawnArea").and_then(value_as_epoch_millis),
+    })
+}
+
+#[tauri::command]
+pub async fn screeps_world_size(
+    request: ScreepsWorldSizeRequest,
+) -> Result<ScreepsWorldSizeResponse, String> {
+    fetch_world_size(request.base_url, request.token, request.username, request.shard).await
+}
+
+#[tauri::command]
+pub async fn screeps_map_rooms(
+    request: ScreepsMapRoomsRequest,
+) -> Result<ScreepsMapRoomsResponse, String> {
+    let size =
+        fetch_world_size(request.base_url, request.token, request.username, request.shard).await?;
+    let half_width = i64::from(size.width / 2);
+    let half_height = i64::from(size.height / 2);
+
+    let rooms = match request.rooms {
+        Some(rooms) => rooms
+            .iter()
+            .map(|room| {
+                room_name_to_xy(&normalize_room_name(room)?)
+                    .map(|(x, y)| map_room_cell(x, y))
+                    .ok_or_else(|| format!("Invalid room name: {}", room))
+            })
+            .collect::<Result<Vec<MapRoomCell>, String>>()?,
+        None => (-half_height..half_height)
+            .flat_map(|y| (-half_width..half_width).map(move |x| map_room_cell(x, y)))
+            .collect(),
+    };
+
+    Ok(ScreepsMapRoomsResponse { width: size.width, height: size.height, rooms })
+}
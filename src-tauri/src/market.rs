@@ -0,0 +1,511 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::http::{
+    clear_response_cache, perform_screeps_request, shared_http_client, ScreepsRequest,
+};
+use crate::resources::validate_resource;
+use crate::rooms::normalize_shard_for;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketOrdersRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub resource_type: Option<String>,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketIndexEntry {
+    pub resource_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketOrder {
+    pub id: String,
+    pub r#type: String,
+    pub resource_type: String,
+    pub price: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_amount: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketOrdersResponse {
+    pub resources: Vec<MarketIndexEntry>,
+    pub orders: Vec<MarketOrder>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketMyOrdersRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketMyOrdersResponse {
+    pub orders: Vec<MarketOrder>,
+    pub locked_credits: f64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketCancelOrderRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub order_id: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketCancelOrderResponse {
+    pub ok: bool,
+    pub order_id: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketHistoryRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub resource_type: String,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketHistoryEntry {
+    pub date: String,
+    pub transactions: f64,
+    pub volume: f64,
+    pub avg_price: f64,
+    pub stddev_price: f64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketDepthRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub resource_type: String,
+    pub shard: Option<String>,
+    pub clear_amount: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketDepthLevel {
+    pub price: f64,
+    pub volume: f64,
+    pub cumulative_volume: f64,
+    pub order_count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketClearEstimate {
+    pub amount: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buy_cost: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sell_proceeds: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMarketDepthResponse {
+    pub resource_type: String,
+    pub bids: Vec<MarketDepthLevel>,
+    pub asks: Vec<MarketDepthLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_bid: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_ask: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spread: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear: Option<MarketClearEstimate>,
+}
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn value_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+fn payload_error(payload: &Value) -> Option<String> {
+    payload.get("error").and_then(value_as_non_empty_string)
+}
+
+fn payload_list(payload: &Value) -> Vec<Value> {
+    for key in ["list", "orders"] {
+        if let Some(items) = payload.get(key).and_then(Value::as_array) {
+            return items.clone();
+        }
+    }
+    if let Some(items) = payload.as_array() {
+        return items.clone();
+    }
+    payload.get("data").map(payload_list).unwrap_or_default()
+}
+
+fn parse_market_order(item: &Value, fallback_resource: Option<&str>) -> Option<MarketOrder> {
+    let id = item.get("_id").or_else(|| item.get("id")).and_then(value_as_non_empty_string)?;
+    let r#type = item.get("type").and_then(value_as_non_empty_string)?.to_ascii_lowercase();
+    if r#type != "buy" && r#type != "sell" {
+        return None;
+    }
+    let resource_type = item
+        .get("resourceType")
+        .and_then(value_as_non_empty_string)
+        .or_else(|| fallback_resource.map(str::to_string))?;
+    Some(MarketOrder {
+        id,
+        r#type,
+        resource_type,
+        price: item.get("price").and_then(value_as_f64)?,
+        amount: item.get("amount").and_then(value_as_f64),
+        remaining_amount: item.get("remainingAmount").and_then(value_as_f64),
+        room_name: item.get("roomName").and_then(value_as_non_empty_string),
+    })
+}
+
+async fn market_request(
+    base_url: &str,
+    token: &str,
+    username: &str,
+    endpoint: &str,
+    method: &str,
+    query: Option<HashMap<String, Value>>,
+    body: Option<Value>,
+) -> Result<Value, String> {
+    if token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: base_url.to_string(),
+            endpoint: endpoint.to_string(),
+            method: Some(method.to_string()),
+            token: Some(token.to_string()),
+            username: Some(username.to_string()),
+            query,
+            body,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("{} request failed: HTTP {}", endpoint, response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+    Ok(response.data)
+}
+
+#[tauri::command]
+pub async fn screeps_market_orders(
+    request: ScreepsMarketOrdersRequest,
+) -> Result<ScreepsMarketOrdersResponse, String> {
+    let mut query = HashMap::<String, Value>::new();
+    if let Some(shard) = normalize_shard_for(&request.base_url, request.shard.as_deref()) {
+        query.insert("shard".to_string(), Value::String(shard));
+    }
+
+    let index_payload = market_request(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        "/api/game/market/orders-index",
+        "GET",
+        Some(query.clone()),
+        None,
+    )
+    .await?;
+    let resources = payload_list(&index_payload)
+        .iter()
+        .filter_map(|item| {
+            Some(MarketIndexEntry {
+                resource_type: item
+                    .get("_id")
+                    .or_else(|| item.get("resourceType"))
+                    .and_then(value_as_non_empty_string)?,
+                count: item.get("count").and_then(value_as_f64),
+            })
+        })
+        .collect::<Vec<MarketIndexEntry>>();
+
+    let resource_type =
+        request.resource_type.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    let Some(resource_type) = resource_type else {
+        return Ok(ScreepsMarketOrdersResponse { resources, orders: Vec::new() });
+    };
+    let resource_type = validate_resource(resource_type)?;
+
+    let orders = fetch_resource_orders(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        &resource_type,
+        query,
+    )
+    .await?;
+
+    Ok(ScreepsMarketOrdersResponse { resources, orders })
+}
+
+async fn fetch_resource_orders(
+    base_url: &str,
+    token: &str,
+    username: &str,
+    resource_type: &str,
+    mut query: HashMap<String, Value>,
+) -> Result<Vec<MarketOrder>, String> {
+    query.insert("resourceType".to_string(), Value::String(resource_type.to_string()));
+    let orders_payload = market_request(
+        base_url,
+        token,
+        username,
+        "/api/game/market/orders",
+        "GET",
+        Some(query),
+        None,
+    )
+    .await?;
+    let mut orders = payload_list(&orders_payload)
+        .iter()
+        .filter_map(|item| parse_market_order(item, Some(resource_type)))
+        .collect::<Vec<MarketOrder>>();
+    orders.sort_by(|left, right| {
+        left.price.partial_cmp(&right.price).unwrap_or(Ordering::Equal).then(left.id.cmp(&right.id))
+    });
+    Ok(orders)
+}
+
+fn order_volume(order: &MarketOrder) -> f64 {
+    order.remaining_amount.or(order.amount).unwrap_or(0.0).max(0.0)
+}
+
+// Expects orders already sorted best price first.
+fn depth_levels(orders: &[&MarketOrder]) -> Vec<MarketDepthLevel> {
+    let mut levels: Vec<MarketDepthLevel> = Vec::new();
+    let mut cumulative_volume = 0.0;
+    for order in orders {
+        let volume = order_volume(order);
+        if volume <= 0.0 {
+            continue;
+        }
+        cumulative_volume += volume;
+        match levels.last_mut() {
+            Some(level) if level.price == order.price => {
+                level.volume += volume;
+                level.cumulative_volume = cumulative_volume;
+                level.order_count += 1;
+            }
+            _ => levels.push(MarketDepthLevel {
+                price: order.price,
+                volume,
+                cumulative_volume,
+                order_count: 1,
+            }),
+        }
+    }
+    levels
+}
+
+// None when the book does not hold enough volume to fill the whole amount.
+fn clearing_credits(levels: &[MarketDepthLevel], amount: f64) -> Option<f64> {
+    let mut remaining = amount;
+    let mut credits = 0.0;
+    for level in levels {
+        if remaining <= 0.0 {
+            break;
+        }
+        let filled = remaining.min(level.volume);
+        credits += filled * level.price;
+        remaining -= filled;
+    }
+    (remaining <= 0.0).then_some(credits)
+}
+
+#[tauri::command]
+pub async fn screeps_market_depth(
+    request: ScreepsMarketDepthRequest,
+) -> Result<ScreepsMarketDepthResponse, String> {
+    let resource_type = validate_resource(&request.resource_type)?;
+    let mut query = HashMap::<String, Value>::new();
+    if let Some(shard) = normalize_shard_for(&request.base_url, request.shard.as_deref()) {
+        query.insert("shard".to_string(), Value::String(shard));
+    }
+    let orders = fetch_resource_orders(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        &resource_type,
+        query,
+    )
+    .await?;
+
+    let asks =
+        depth_levels(&orders.iter().filter(|order| order.r#type == "sell").collect::<Vec<_>>());
+    let bids = depth_levels(
+        &orders.iter().rev().filter(|order| order.r#type == "buy").collect::<Vec<_>>(),
+    );
+    let best_bid = bids.first().map(|level| level.price);
+    let best_ask = asks.first().map(|level| level.price);
+    let clear =
+        request.clear_amount.filter(|amount| *amount > 0.0).map(|amount| MarketClearEstimate {
+            amount,
+            buy_cost: clearing_credits(&asks, amount),
+            sell_proceeds: clearing_credits(&bids, amount),
+        });
+
+    Ok(ScreepsMarketDepthResponse {
+        resource_type,
+        spread: best_bid.zip(best_ask).map(|(bid, ask)| ask - bid),
+        bids,
+        asks,
+        best_bid,
+        best_ask,
+        clear,
+    })
+}
+
+#[tauri::command]
+pub async fn screeps_market_my_orders(
+    request: ScreepsMarketMyOrdersRequest,
+) -> Result<ScreepsMarketMyOrdersResponse, String> {
+    let payload = market_request(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        "/api/game/market/my-orders",
+        "GET",
+        None,
+        None,
+    )
+    .await?;
+
+    let mut orders = payload_list(&payload)
+        .iter()
+        .filter(|item| item.get("active").and_then(Value::as_bool) != Some(false))
+        .filter_map(|item| parse_market_order(item, None))
+        .filter(|order| order.remaining_amount.is_none_or(|amount| amount > 0.0))
+        .collect::<Vec<MarketOrder>>();
+    orders.sort_by(|left, right| {
+        left.resource_type.cmp(&right.resource_type).then(left.id.cmp(&right.id))
+    });
+    let locked_credits = orders
+        .iter()
+        .filter(|order| order.r#type == "buy")
+        .map(|order| order.price * order.remaining_amount.unwrap_or(0.0))
+        .sum();
+
+    Ok(ScreepsMarketMyOrdersResponse { orders, locked_credits })
+}
+
+#[tauri::command]
+pub async fn screeps_market_cancel_order(
+    request: ScreepsMarketCancelOrderRequest,
+) -> Result<ScreepsMarketCancelOrderResponse, String> {
+    let order_id = request.order_id.trim().to_string();
+    if order_id.is_empty() {
+        return Err("Order id cannot be empty".to_string());
+    }
+
+    let payload = market_request(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        "/api/game/market/cancel-order",
+        "POST",
+        None,
+        Some(json!({ "orderId": order_id })),
+    )
+    .await?;
+    if payload.get("ok").and_then(value_as_f64) == Some(0.0) {
+        return Err(format!("failed to cancel order {}", order_id));
+    }
+
+    clear_response_cache(Some(&request.base_url), Some("/api/game/market/"));
+    Ok(ScreepsMarketCancelOrderResponse { ok: true, order_id })
+}
+
+#[tauri::command]
+pub async fn screeps_market_history(
+    request: ScreepsMarketHistoryRequest,
+) -> Result<Vec<MarketHistoryEntry>, String> {
+    let resource_type = validate_resource(&request.resource_type)?;
+    let mut query =
+        HashMap::from([("resourceType".to_string(), Value::String(resource_type.clone()))]);
+    if let Some(shard) = normalize_shard_for(&request.base_url, request.shard.as_deref()) {
+        query.insert("shard".to_string(), Value::String(shard));
+    }
+
+    let payload = market_request(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        "/api/game/market/stats",
+        "GET",
+        Some(query),
+        None,
+    )
+    .await?;
+
+    let items = payload.get("stats").and_then(Value::as_array).cloned().unwrap_or_default();
+    let mut history = items
+        .iter()
+        .filter(|item| {
+            item.get("resourceType")
+                .and_then(Value::as_str)
+                .is_none_or(|value| value == resource_type)
+        })
+        .filter_map(|item| {
+            Some(MarketHistoryEntry {
+                date: item.get("date").and_then(value_as_non_empty_string)?,
+                transactions: item.get("transactions").and_then(value_as_f64).unwrap_or(0.0),
+                volume: item.get("volume").and_then(value_as_f64).unwrap_or(0.0),
+                avg_price: item.get("avgPrice").and_then(value_as_f64).unwrap_or(0.0),
+                stddev_price: item.get("stddevPrice").and_then(value_as_f64).unwrap_or(0.0),
+            })
+        })
+        .collect::<Vec<MarketHistoryEntry>>();
+    history.sort_by(|left, right| left.date.cmp(&right.date));
+
+    Ok(history)
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::auth::fetch_auth_profile;
+use crate::http::{
+    clear_response_cache, decode_gz_text, perform_screeps_request, shared_http_client,
+    ScreepsRequest,
+};
+use crate::rooms::normalize_shard_for;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMemoryGetRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub path: Option<String>,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMemorySetRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub path: String,
+    pub value: Value,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMemorySetResponse {
+    pub ok: bool,
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRuntimeStatsRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub path: Option<String>,
+    pub shard: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRuntimeStatsResponse {
+    pub path: String,
+    pub stats: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_shard: Option<HashMap<String, f64>>,
+}
+
+const DEFAULT_RUNTIME_STATS_PATH: &str = "stats";
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn payload_error(payload: &Value) -> Option<String> {
+    payload.get("error").and_then(value_as_non_empty_string)
+}
+
+fn normalize_memory_path(path: &str) -> Result<String, String> {
+    let trimmed = path.trim();
+    let trimmed = trimmed.strip_prefix("Memory.").unwrap_or(trimmed);
+    if trimmed.is_empty() {
+        return Err("Memory path cannot be empty".to_string());
+    }
+    if trimmed.chars().any(char::is_control) {
+        return Err("Memory path must not contain control characters".to_string());
+    }
+    if trimmed.split('.').any(|segment| segment.trim().is_empty()) {
+        return Err(format!("Invalid memory path: {}", trimmed));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn decode_memory_data(data: Value) -> Result<Value, String> {
+    let Some(text) = data.as_str() else {
+        return Ok(data);
+    };
+    if !text.trim_start().starts_with("gz:") {
+        return Ok(data);
+    }
+    let decoded = decode_gz_text(text)
+        .ok_or_else(|| "failed to decode compressed memory payload".to_string())?;
+    serde_json::from_str::<Value>(&decoded)
+        .map_err(|error| format!("failed to parse memory payload: {}", error))
+}
+
+#[tauri::command]
+pub async fn screeps_memory_get(request: ScreepsMemoryGetRequest) -> Result<Value, String> {
+    fetch_memory(request).await
+}
+
+async fn fetch_memory(request: ScreepsMemoryGetRequest) -> Result<Value, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let mut query = HashMap::new();
+    if let Some(path) = request.path.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        query.insert("path".to_string(), Value::String(path.to_string()));
+    }
+    if let Some(shard) = normalize_shard_for(&request.base_url, request.shard.as_deref()) {
+        query.insert("shard".to_string(), Value::String(shard));
+    }
+
+    let client = shared_http_client()?;
+    let mut response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url,
+            endpoint: "/api/user/memory".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(request.token),
+            username: Some(request.username),
+            query: Some(query),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("memory request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+
+    // Undefined paths come back without a `data` field at all.
+    let data = response.data.get_mut("data").map(Value::take).unwrap_or(Value::Null);
+    decode_memory_data(data)
+}
+
+#[tauri::command]
+pub async fn screeps_memory_set(
+    request: ScreepsMemorySetRequest,
+) -> Result<ScreepsMemorySetResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let path = normalize_memory_path(&request.path)?;
+    let mut body = json!({ "path": path, "value": request.value });
+    if let Some(shard) = normalize_shard_for(&request.base_url, request.shard.as_deref()) {
+        body["shard"] = Value::String(shard);
+    }
+
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url.clone(),
+            endpoint: "/api/user/memory".to_string(),
+            method: Some("POST".to_string()),
+            token: Some(request.token),
+            username: Some(request.username),
+            body: Some(body),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("memory update failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+
+    clear_response_cache(Some(&request.base_url), Some("/api/user/memory"));
+    Ok(ScreepsMemorySetResponse {
+        ok: response.data.get("ok").and_then(Value::as_i64) == Some(1),
+        path,
+    })
+}
+
+// The server has no CPU history of its own; this only works for bots that export stats to Memory.
+#[tauri::command]
+pub async fn screeps_runtime_stats(
+    request: ScreepsRuntimeStatsRequest,
+) -> Result<ScreepsRuntimeStatsResponse, String> {
+    let path =
+        normalize_memory_path(request.path.as_deref().unwrap_or(DEFAULT_RUNTIME_STATS_PATH))?;
+    let stats = fetch_memory(ScreepsMemoryGetRequest {
+        base_url: request.base_url.clone(),
+        token: request.token.clone(),
+        username: request.username,
+        path: Some(path.clone()),
+        shard: request.shard,
+    })
+    .await?;
+    let empty = match &stats {
+        Value::Null => true,
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    };
+    if empty {
+        return Err(format!("No stats found at Memory.{}", path));
+    }
+
+    let profile = fetch_auth_profile(&request.base_url, &request.token).await.ok();
+    Ok(ScreepsRuntimeStatsResponse {
+        path,
+        stats,
+        cpu_limit: profile.as_ref().and_then(|profile| profile.cpu),
+        cpu_shard: profile.and_then(|profile| profile.cpu_shard),
+    })
+}
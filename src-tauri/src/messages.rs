@@ -2,13 +2,28 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest};
+use crate::auth::fetch_auth_profile;
+use crate::http::{
+    clear_response_cache, perform_screeps_request, shared_http_client, update_cached_responses,
+    ScreepsRequest,
+};
 
 const DEFAULT_PER_CONVERSATION_LIMIT: usize = 200;
 const DEFAULT_MAX_CONVERSATIONS: usize = 200;
 const MAX_PER_CONVERSATION_LIMIT: usize = 1000;
 const MAX_CONVERSATIONS_LIMIT: usize = 500;
+const HYDRATE_CONCURRENCY: usize = 6;
+const MAX_CLEAR_PAGES: usize = 20;
+const DEFAULT_SEARCH_DEPTH: usize = 50;
+// The official server and private-server mods expose message deletion under different shapes.
+const DELETE_VARIANTS: [(&str, &str); 4] = [
+    ("/api/user/messages/delete", "id"),
+    ("/api/user/messages/delete", "messageId"),
+    ("/api/user/messages/remove", "id"),
+    ("/api/user/messages/remove", "messageId"),
+];
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -17,6 +32,7 @@ pub struct ScreepsMessagesFetchRequest {
     pub token: String,
     pub username: String,
     pub max_conversations: Option<usize>,
+    pub hydrate_depth: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -30,6 +46,8 @@ pub struct ScreepsMessagesThreadRequest {
     pub peer_avatar_url: Option<String>,
     pub peer_has_badge: Option<bool>,
     pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub mark_read: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -51,6 +69,112 @@ pub struct ScreepsMessagesSendResponse {
     pub feedback: Option<String>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessagesUnreadCountResponse {
+    pub conversations: usize,
+    pub messages: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessagesMarkReadRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub peer_id: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessagesMarkReadResponse {
+    pub ok: bool,
+    pub peer_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unread_count: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessageSendRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub peer_id: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessageSendResponse {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessagesDeleteRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub message_id: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsConversationClearRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub peer_id: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessagesSearchRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub query: String,
+    pub max_conversations: Option<usize>,
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageMatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSearchHit {
+    pub message: ScreepsConversationMessageDto,
+    pub ranges: Vec<MessageMatchRange>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSearchResult {
+    pub peer_id: String,
+    pub peer_username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_avatar_url: Option<String>,
+    pub hits: Vec<MessageSearchHit>,
+    // Set when the thread could not be loaded, so only its latest message was searched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hydration_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMessagesDeleteResponse {
+    pub deleted: usize,
+    pub failed: usize,
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ScreepsMessageParticipantDto {
@@ -85,14 +209,9 @@ pub struct ScreepsConversationDto {
     pub peer_avatar_url: Option<String>,
     pub peer_has_badge: bool,
     pub messages: Vec<ScreepsConversationMessageDto>,
-}
-
-#[derive(Debug, Deserialize)]
-struct AuthMeResponse {
-    ok: i64,
-    #[serde(rename = "_id")]
-    self_id: String,
-    username: String,
+    pub has_more: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hydration_error: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -299,39 +418,6 @@ fn to_conversation_message(
     })
 }
 
-async fn fetch_auth_profile(
-    request: &ScreepsMessagesFetchRequest,
-) -> Result<AuthMeResponse, String> {
-    let client = shared_http_client()?;
-    let response = perform_screeps_request(
-        client,
-        ScreepsRequest {
-            base_url: request.base_url.clone(),
-            endpoint: "/api/auth/me".to_string(),
-            method: Some("GET".to_string()),
-            token: Some(request.token.clone()),
-            username: None,
-            query: None,
-            body: None,
-        },
-    )
-    .await?;
-
-    if !response.ok {
-        return Err(format!("auth profile request failed: HTTP {}", response.status));
-    }
-    if let Some(error) = payload_error(&response.data) {
-        return Err(error);
-    }
-
-    let payload = serde_json::from_value::<AuthMeResponse>(response.data)
-        .map_err(|error| format!("failed to parse /api/auth/me payload: {}", error))?;
-    if payload.ok != 1 {
-        return Err("auth profile returned ok!=1".to_string());
-    }
-    Ok(payload)
-}
-
 async fn fetch_messages_index(
     request: &ScreepsMessagesFetchRequest,
     limit: usize,
@@ -341,7 +427,7 @@ async fn fetch_messages_index(
     query.insert("limit".to_string(), json!(limit));
 
     let response = perform_screeps_request(
-        client,
+        &client,
         ScreepsRequest {
             base_url: request.base_url.clone(),
             endpoint: "/api/user/messages/index".to_string(),
@@ -350,6 +436,7 @@ async fn fetch_messages_index(
             username: Some(request.username.clone()),
             query: Some(query),
             body: None,
+            ..Default::default()
         },
     )
     .await?;
@@ -373,15 +460,16 @@ async fn fetch_messages_list(
     request: &ScreepsMessagesFetchRequest,
     peer_id: &str,
     count: usize,
+    offset: usize,
 ) -> Result<MessagesListResponse, String> {
     let client = shared_http_client()?;
     let mut query = HashMap::<String, Value>::new();
     query.insert("respondent".to_string(), json!(peer_id));
     query.insert("count".to_string(), json!(count));
-    query.insert("offset".to_string(), json!(0));
+    query.insert("offset".to_string(), json!(offset));
 
     let response = perform_screeps_request(
-        client,
+        &client,
         ScreepsRequest {
             base_url: request.base_url.clone(),
             endpoint: "/api/user/messages/list".to_string(),
@@ -390,6 +478,7 @@ async fn fetch_messages_list(
             username: Some(request.username.clone()),
             query: Some(query),
             body: None,
+            ..Default::default()
         },
     )
     .await?;
@@ -412,6 +501,69 @@ async fn fetch_messages_list(
     Ok(payload)
 }
 
+async fn mark_conversation_read(
+    base_url: &str,
+    token: &str,
+    username: &str,
+    peer_id: &str,
+) -> Result<(), String> {
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: base_url.to_string(),
+            endpoint: "/api/user/messages/mark-read".to_string(),
+            method: Some("POST".to_string()),
+            token: Some(token.to_string()),
+            username: Some(username.to_string()),
+            query: None,
+            body: Some(json!({ "id": peer_id })),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!(
+            "messages mark-read request failed for {}: HTTP {}",
+            peer_id, response.status
+        ));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(format!("messages mark-read returned error for {}: {}", peer_id, error));
+    }
+
+    clear_response_cache(Some(base_url), Some("/api/user/messages/"));
+    Ok(())
+}
+
+async fn hydrate_conversations(
+    request: &ScreepsMessagesFetchRequest,
+    peer_ids: Vec<String>,
+    depth: usize,
+) -> Result<HashMap<String, Result<Vec<RawMessage>, String>>, String> {
+    let mut output = HashMap::<String, Result<Vec<RawMessage>, String>>::new();
+    for chunk in peer_ids.chunks(HYDRATE_CONCURRENCY) {
+        let mut handles = Vec::with_capacity(chunk.len());
+        for peer_id in chunk {
+            let task_request = request.clone();
+            let peer_id = peer_id.clone();
+            handles.push(tauri::async_runtime::spawn(async move {
+                let result = fetch_messages_list(&task_request, &peer_id, depth, 0).await;
+                (peer_id, result)
+            }));
+        }
+
+        for handle in handles {
+            let (peer_id, result) =
+                handle.await.map_err(|error| format!("messages hydrate task failed: {}", error))?;
+            // One failed thread must not sink the rest; callers report it per peer.
+            output.insert(peer_id, result.map(|payload| payload.messages));
+        }
+    }
+    Ok(output)
+}
+
 fn conversation_heads_from_index(
     base_url: &str,
     index_payload: MessagesIndexResponse,
@@ -462,6 +614,28 @@ fn conversation_heads_from_index(
     heads
 }
 
+// Converts raw messages into DTOs, dropping duplicate ids, oldest first.
+fn conversation_messages(
+    raw_messages: impl IntoIterator<Item = RawMessage>,
+    self_id: &str,
+    self_username: &str,
+    head: &ConversationHead,
+) -> Vec<ScreepsConversationMessageDto> {
+    let mut messages = Vec::<ScreepsConversationMessageDto>::new();
+    let mut seen = HashSet::<String>::new();
+    for raw in raw_messages {
+        if let Some(message) =
+            to_conversation_message(raw, self_id, self_username, &head.peer_id, &head.peer_username)
+        {
+            if seen.insert(message.id.clone()) {
+                messages.push(message);
+            }
+        }
+    }
+    messages.sort_by(compare_message_time_asc);
+    messages
+}
+
 #[tauri::command]
 pub async fn screeps_messages_fetch(
     request: ScreepsMessagesFetchRequest,
@@ -478,7 +652,7 @@ pub async fn screeps_messages_fetch(
         .unwrap_or(DEFAULT_MAX_CONVERSATIONS)
         .clamp(1, MAX_CONVERSATIONS_LIMIT);
 
-    let auth_profile = fetch_auth_profile(&request).await?;
+    let auth_profile = fetch_auth_profile(&request.base_url, &request.token).await?;
     let self_id = auth_profile.self_id;
     let self_username = auth_profile.username;
 
@@ -488,18 +662,38 @@ pub async fn screeps_messages_fetch(
     }
 
     let heads = conversation_heads_from_index(&request.base_url, index_payload, max_conversations);
+    let hydrate_depth =
+        request.hydrate_depth.map(|depth| depth.clamp(1, MAX_PER_CONVERSATION_LIMIT));
+    let mut hydrated = match hydrate_depth {
+        Some(depth) => {
+            let peer_ids = heads.iter().map(|head| head.peer_id.clone()).collect();
+            hydrate_conversations(&request, peer_ids, depth).await?
+        }
+        None => HashMap::new(),
+    };
 
     let mut output = HashMap::<String, ScreepsConversationDto>::new();
     for head in heads {
-        let mut messages = Vec::<ScreepsConversationMessageDto>::new();
-        if let Some(message) = to_conversation_message(
-            head.latest_message,
+        let (history, hydration_error) = match hydrated.remove(&head.peer_id) {
+            Some(Ok(items)) => (Some(items), None),
+            Some(Err(error)) => (None, Some(error)),
+            None => (None, None),
+        };
+        let has_more = match (hydrate_depth, history.as_ref()) {
+            (Some(depth), Some(items)) => items.len() >= depth,
+            _ => true,
+        };
+        let mut messages = conversation_messages(
+            history.into_iter().flatten().chain(std::iter::once(head.latest_message.clone())),
             &self_id,
             &self_username,
-            &head.peer_id,
-            &head.peer_username,
-        ) {
-            messages.push(message);
+            &head,
+        );
+        if let Some(depth) = hydrate_depth {
+            if messages.len() > depth {
+                let drain_count = messages.len() - depth;
+                messages.drain(0..drain_count);
+            }
         }
 
         output.insert(
@@ -510,6 +704,8 @@ pub async fn screeps_messages_fetch(
                 peer_avatar_url: head.peer_avatar_url,
                 peer_has_badge: head.peer_has_badge,
                 messages,
+                has_more,
+                hydration_error,
             },
         );
     }
@@ -517,6 +713,36 @@ pub async fn screeps_messages_fetch(
     Ok(output)
 }
 
+#[tauri::command]
+pub async fn screeps_messages_unread_count(
+    request: ScreepsMessagesFetchRequest,
+) -> Result<ScreepsMessagesUnreadCountResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    if request.username.trim().is_empty() {
+        return Err("Username cannot be empty".to_string());
+    }
+
+    let limit = request
+        .max_conversations
+        .unwrap_or(MAX_CONVERSATIONS_LIMIT)
+        .clamp(1, MAX_CONVERSATIONS_LIMIT);
+    let index_payload = fetch_messages_index(&request, limit).await?;
+
+    let mut peers = HashSet::<String>::new();
+    let mut messages = 0;
+    for item in index_payload.messages {
+        if !item.message.unread || !item.message.kind.trim().eq_ignore_ascii_case("in") {
+            continue;
+        }
+        messages += 1;
+        peers.insert(item.peer_id.trim().to_string());
+    }
+
+    Ok(ScreepsMessagesUnreadCountResponse { conversations: peers.len(), messages })
+}
+
 #[tauri::command]
 pub async fn screeps_messages_fetch_thread(
     request: ScreepsMessagesThreadRequest,
@@ -550,13 +776,16 @@ pub async fn screeps_messages_fetch_thread(
         token: request.token,
         username: request.username,
         max_conversations: Some(1),
+        hydrate_depth: None,
     };
 
-    let auth_profile = fetch_auth_profile(&fetch_request).await?;
+    let auth_profile = fetch_auth_profile(&fetch_request.base_url, &fetch_request.token).await?;
     let self_id = auth_profile.self_id;
     let self_username = auth_profile.username;
 
-    let list_payload = fetch_messages_list(&fetch_request, &peer_id, per_limit).await?;
+    let offset = request.offset.unwrap_or(0);
+    let list_payload = fetch_messages_list(&fetch_request, &peer_id, per_limit, offset).await?;
+    let has_more = list_payload.messages.len() >= per_limit;
     let mut messages = Vec::<ScreepsConversationMessageDto>::new();
     let mut seen = HashSet::<String>::new();
     for raw in list_payload.messages {
@@ -575,45 +804,74 @@ pub async fn screeps_messages_fetch_thread(
         messages.drain(0..drain_count);
     }
 
-    Ok(ScreepsConversationDto { peer_id, peer_username, peer_avatar_url, peer_has_badge, messages })
+    let has_unread_inbound = messages
+        .iter()
+        .any(|message| message.direction == "inbound" && message.unread == Some(true));
+    if request.mark_read.unwrap_or(false) && has_unread_inbound {
+        mark_conversation_read(
+            &fetch_request.base_url,
+            &fetch_request.token,
+            &fetch_request.username,
+            &peer_id,
+        )
+        .await?;
+        for message in messages.iter_mut().filter(|message| message.direction == "inbound") {
+            message.unread = Some(false);
+        }
+    }
+
+    Ok(ScreepsConversationDto {
+        peer_id,
+        peer_username,
+        peer_avatar_url,
+        peer_has_badge,
+        messages,
+        has_more,
+        hydration_error: None,
+    })
 }
 
-#[tauri::command]
-pub async fn screeps_messages_send(
-    request: ScreepsMessagesSendRequest,
-) -> Result<ScreepsMessagesSendResponse, String> {
-    if request.token.trim().is_empty() {
+async fn post_message(
+    base_url: &str,
+    token: &str,
+    username: &str,
+    respondent: &str,
+    subject: Option<&str>,
+    text: &str,
+) -> Result<Value, String> {
+    if token.trim().is_empty() {
         return Err("Token cannot be empty".to_string());
     }
-    if request.username.trim().is_empty() {
+    if username.trim().is_empty() {
         return Err("Username cannot be empty".to_string());
     }
 
-    let respondent = request.respondent.trim().to_string();
+    let respondent = respondent.trim().to_string();
     if respondent.is_empty() {
         return Err("Respondent cannot be empty".to_string());
     }
-    let text = request.text.trim().to_string();
+    let text = text.trim().to_string();
     if text.is_empty() {
         return Err("Message body cannot be empty".to_string());
     }
-    let subject = request.subject.unwrap_or_default().trim().to_string();
+    let subject = subject.unwrap_or_default().trim().to_string();
 
     let client = shared_http_client()?;
     let response = perform_screeps_request(
-        client,
+        &client,
         ScreepsRequest {
-            base_url: request.base_url,
+            base_url: base_url.to_string(),
             endpoint: "/api/user/messages/send".to_string(),
             method: Some("POST".to_string()),
-            token: Some(request.token),
-            username: Some(request.username),
+            token: Some(token.to_string()),
+            username: Some(username.to_string()),
             query: None,
             body: Some(json!({
                 "respondent": respondent,
                 "subject": subject,
                 "text": text,
             })),
+            ..Default::default()
         },
     )
     .await?;
@@ -625,5 +883,398 @@ pub async fn screeps_messages_send(
         return Err(error);
     }
 
-    Ok(ScreepsMessagesSendResponse { ok: true, feedback: payload_feedback(&response.data) })
+    // The cached thread is patched in place; only the index (last message per peer) goes stale.
+    match created_message_id(&response.data) {
+        Some(message_id) => {
+            append_cached_message(base_url, &respondent, &message_id, &text);
+            clear_response_cache(Some(base_url), Some("/api/user/messages/index"));
+        }
+        None => {
+            clear_response_cache(Some(base_url), Some("/api/user/messages/"));
+        }
+    }
+    Ok(response.data)
+}
+
+fn append_cached_message(base_url: &str, respondent: &str, message_id: &str, text: &str) {
+    let message = json!({
+        "_id": message_id,
+        "date": now_iso8601(),
+        "type": "out",
+        "text": text,
+        "unread": true,
+    });
+    update_cached_responses(base_url, "/api/user/messages/list", |query, data| {
+        let newest_page = query.get("offset").is_none_or(|offset| offset == "0");
+        if !newest_page || query.get("respondent").map(String::as_str) != Some(respondent) {
+            return false;
+        }
+        let Some(messages) = data.get_mut("messages").and_then(Value::as_array_mut) else {
+            return false;
+        };
+        if messages.iter().any(|item| item.get("_id").and_then(Value::as_str) == Some(message_id)) {
+            return false;
+        }
+        messages.insert(0, message.clone());
+        true
+    });
+}
+
+// Matches the server's `Date#toISOString` format so cached and fetched messages sort together.
+fn now_iso8601() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0);
+    let days = millis.div_euclid(86_400_000);
+    let day_millis = millis.rem_euclid(86_400_000);
+    // Civil-from-days, proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        day_millis / 3_600_000,
+        day_millis / 60_000 % 60,
+        day_millis / 1_000 % 60,
+        day_millis % 1_000
+    )
+}
+
+fn created_message_id(payload: &Value) -> Option<String> {
+    payload
+        .get("_id")
+        .or_else(|| payload.get("id"))
+        .or_else(|| payload.get("message").and_then(|message| message.get("_id")))
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[tauri::command]
+pub async fn screeps_messages_send(
+    request: ScreepsMessagesSendRequest,
+) -> Result<ScreepsMessagesSendResponse, String> {
+    let payload = post_message(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        &request.respondent,
+        request.subject.as_deref(),
+        &request.text,
+    )
+    .await?;
+
+    Ok(ScreepsMessagesSendResponse { ok: true, feedback: payload_feedback(&payload) })
+}
+
+#[tauri::command]
+pub async fn screeps_messages_mark_read(
+    request: ScreepsMessagesMarkReadRequest,
+) -> Result<ScreepsMessagesMarkReadResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    if request.username.trim().is_empty() {
+        return Err("Username cannot be empty".to_string());
+    }
+    let peer_id = request.peer_id.trim().to_string();
+    if peer_id.is_empty() {
+        return Err("Peer id cannot be empty".to_string());
+    }
+
+    mark_conversation_read(&request.base_url, &request.token, &request.username, &peer_id).await?;
+    // The read already succeeded, so a failed count lookup only omits the field.
+    let unread_count =
+        fetch_server_unread_count(&request.base_url, &request.token, &request.username).await.ok();
+    Ok(ScreepsMessagesMarkReadResponse { ok: true, peer_id, unread_count })
+}
+
+async fn fetch_server_unread_count(
+    base_url: &str,
+    token: &str,
+    username: &str,
+) -> Result<u64, String> {
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: base_url.to_string(),
+            endpoint: "/api/user/messages/unread-count".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(token.to_string()),
+            username: Some(username.to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    if !response.ok {
+        return Err(format!("messages unread-count request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = payload_error(&response.data) {
+        return Err(error);
+    }
+    response
+        .data
+        .get("count")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "messages unread-count response did not include a count".to_string())
+}
+
+#[tauri::command]
+pub async fn screeps_message_send(
+    request: ScreepsMessageSendRequest,
+) -> Result<ScreepsMessageSendResponse, String> {
+    let payload = post_message(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        &request.peer_id,
+        None,
+        &request.text,
+    )
+    .await?;
+
+    Ok(ScreepsMessageSendResponse { ok: true, message_id: created_message_id(&payload) })
+}
+
+async fn delete_message(
+    base_url: &str,
+    token: &str,
+    username: &str,
+    message_id: &str,
+    preferred_variant: &mut Option<usize>,
+) -> Result<(), String> {
+    let client = shared_http_client()?;
+    let mut order = (0..DELETE_VARIANTS.len()).collect::<Vec<usize>>();
+    if let Some(preferred) = *preferred_variant {
+        order.retain(|index| *index != preferred);
+        order.insert(0, preferred);
+    }
+
+    let mut failures = Vec::<String>::new();
+    for index in order {
+        let (endpoint, key) = DELETE_VARIANTS[index];
+        let response = match perform_screeps_request(
+            &client,
+            ScreepsRequest {
+                base_url: base_url.to_string(),
+                endpoint: endpoint.to_string(),
+                method: Some("POST".to_string()),
+                token: Some(token.to_string()),
+                username: Some(username.to_string()),
+                body: Some(json!({ key: message_id })),
+                ..Default::default()
+            },
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                failures.push(error);
+                continue;
+            }
+        };
+        if !response.ok {
+            failures.push(format!("{}: HTTP {}", endpoint, response.status));
+            continue;
+        }
+        if let Some(error) = payload_error(&response.data) {
+            failures.push(format!("{}: {}", endpoint, error));
+            continue;
+        }
+        *preferred_variant = Some(index);
+        return Ok(());
+    }
+
+    let reason = failures.into_iter().next().unwrap_or_else(|| "Unknown error".to_string());
+    Err(format!("Failed to delete message {}: {}", message_id, reason))
+}
+
+#[tauri::command]
+pub async fn screeps_messages_delete(
+    request: ScreepsMessagesDeleteRequest,
+) -> Result<ScreepsMessagesDeleteResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let message_id = request.message_id.trim();
+    if message_id.is_empty() {
+        return Err("Message id cannot be empty".to_string());
+    }
+
+    delete_message(&request.base_url, &request.token, &request.username, message_id, &mut None)
+        .await?;
+    clear_response_cache(Some(&request.base_url), Some("/api/user/messages/"));
+    Ok(ScreepsMessagesDeleteResponse { deleted: 1, failed: 0 })
+}
+
+#[tauri::command]
+pub async fn screeps_conversation_clear(
+    request: ScreepsConversationClearRequest,
+) -> Result<ScreepsMessagesDeleteResponse, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    if request.username.trim().is_empty() {
+        return Err("Username cannot be empty".to_string());
+    }
+    let peer_id = request.peer_id.trim().to_string();
+    if peer_id.is_empty() {
+        return Err("Peer id cannot be empty".to_string());
+    }
+
+    let fetch_request = ScreepsMessagesFetchRequest {
+        base_url: request.base_url.clone(),
+        token: request.token.clone(),
+        username: request.username.clone(),
+        max_conversations: Some(1),
+        hydrate_depth: None,
+    };
+    // Collect every id first; deleting while paging would shift the offsets under us.
+    let mut message_ids = Vec::<String>::new();
+    let mut seen = HashSet::<String>::new();
+    for page in 0..MAX_CLEAR_PAGES {
+        let payload = fetch_messages_list(
+            &fetch_request,
+            &peer_id,
+            MAX_PER_CONVERSATION_LIMIT,
+            page * MAX_PER_CONVERSATION_LIMIT,
+        )
+        .await?;
+        let page_len = payload.messages.len();
+        for message in payload.messages {
+            let id = message.id.trim().to_string();
+            if !id.is_empty() && seen.insert(id.clone()) {
+                message_ids.push(id);
+            }
+        }
+        if page_len < MAX_PER_CONVERSATION_LIMIT {
+            break;
+        }
+    }
+
+    let mut preferred_variant = None;
+    let mut deleted = 0;
+    let mut failed = 0;
+    for message_id in &message_ids {
+        match delete_message(
+            &request.base_url,
+            &request.token,
+            &request.username,
+            message_id,
+            &mut preferred_variant,
+        )
+        .await
+        {
+            Ok(()) => deleted += 1,
+            // No variant has worked yet, so the server does not support deletion at all.
+            Err(error) if preferred_variant.is_none() => return Err(error),
+            Err(_) => failed += 1,
+        }
+    }
+
+    if deleted > 0 {
+        clear_response_cache(Some(&request.base_url), Some("/api/user/messages/"));
+    }
+    Ok(ScreepsMessagesDeleteResponse { deleted, failed })
+}
+
+fn fold_char(value: char) -> char {
+    value.to_lowercase().next().unwrap_or(value)
+}
+
+// Non-overlapping case-insensitive matches, as char offsets into the original text.
+fn match_ranges(text: &str, needle: &[char]) -> Vec<MessageMatchRange> {
+    let haystack = text.chars().map(fold_char).collect::<Vec<char>>();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while !needle.is_empty() && start + needle.len() <= haystack.len() {
+        if haystack[start..start + needle.len()] == *needle {
+            ranges.push(MessageMatchRange { start, end: start + needle.len() });
+            start += needle.len();
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+#[tauri::command]
+pub async fn screeps_messages_search(
+    request: ScreepsMessagesSearchRequest,
+) -> Result<Vec<MessageSearchResult>, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    if request.username.trim().is_empty() {
+        return Err("Username cannot be empty".to_string());
+    }
+    let needle = request.query.trim().chars().map(fold_char).collect::<Vec<char>>();
+    if needle.is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+
+    let max_conversations = request
+        .max_conversations
+        .unwrap_or(DEFAULT_MAX_CONVERSATIONS)
+        .clamp(1, MAX_CONVERSATIONS_LIMIT);
+    let depth = request.depth.unwrap_or(DEFAULT_SEARCH_DEPTH).clamp(1, MAX_PER_CONVERSATION_LIMIT);
+    let fetch_request = ScreepsMessagesFetchRequest {
+        base_url: request.base_url.clone(),
+        token: request.token,
+        username: request.username,
+        max_conversations: Some(max_conversations),
+        hydrate_depth: Some(depth),
+    };
+
+    let auth_profile = fetch_auth_profile(&fetch_request.base_url, &fetch_request.token).await?;
+    let index_payload = fetch_messages_index(&fetch_request, max_conversations).await?;
+    let heads =
+        conversation_heads_from_index(&fetch_request.base_url, index_payload, max_conversations);
+    let peer_ids = heads.iter().map(|head| head.peer_id.clone()).collect();
+    let mut hydrated = hydrate_conversations(&fetch_request, peer_ids, depth).await?;
+
+    let mut results = Vec::new();
+    for head in heads {
+        let (history, hydration_error) = match hydrated.remove(&head.peer_id) {
+            Some(Ok(items)) => (items, None),
+            Some(Err(error)) => (Vec::new(), Some(error)),
+            None => (Vec::new(), None),
+        };
+        let messages = conversation_messages(
+            history.into_iter().chain(std::iter::once(head.latest_message.clone())),
+            &auth_profile.self_id,
+            &auth_profile.username,
+            &head,
+        );
+        let hits = messages
+            .into_iter()
+            .filter_map(|message| {
+                let ranges = match_ranges(message.text.as_deref()?, &needle);
+                (!ranges.is_empty()).then_some(MessageSearchHit { message, ranges })
+            })
+            .collect::<Vec<MessageSearchHit>>();
+        if !hits.is_empty() || hydration_error.is_some() {
+            results.push(MessageSearchResult {
+                peer_id: head.peer_id,
+                peer_username: head.peer_username,
+                peer_avatar_url: head.peer_avatar_url,
+                hits,
+                hydration_error,
+            });
+        }
+    }
+    Ok(results)
 }
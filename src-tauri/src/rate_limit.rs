@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const BUCKET_CAPACITY: f64 = 20.0;
+const BUCKET_REFILL_PER_SEC: f64 = 10.0;
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+const MAX_RETRY_AFTER_SECS: u64 = 3_600;
+
+static HOST_LIMITERS: OnceLock<Mutex<HashMap<String, HostLimiter>>> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+struct HostLimiter {
+    tokens: f64,
+    refilled_at: Instant,
+    blocked_until: Option<Instant>,
+}
+
+impl HostLimiter {
+    fn new(now: Instant) -> Self {
+        HostLimiter { tokens: BUCKET_CAPACITY, refilled_at: now, blocked_until: None }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * BUCKET_REFILL_PER_SEC).min(BUCKET_CAPACITY);
+        self.refilled_at = now;
+    }
+}
+
+fn host_limiters() -> &'static Mutex<HashMap<String, HostLimiter>> {
+    HOST_LIMITERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    // IMF-fixdate, e.g. "Sun, 06 Nov 1994 08:49:37 GMT".
+    let parts = value.split_whitespace().collect::<Vec<&str>>();
+    if parts.len() != 6 || !parts[5].eq_ignore_ascii_case("GMT") {
+        return None;
+    }
+    let day = parts[1].parse::<i64>().ok()?;
+    let month = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"]
+        .iter()
+        .position(|name| parts[2].eq_ignore_ascii_case(name))? as i64
+        + 1;
+    let year = parts[3].parse::<i64>().ok()?;
+    let clock = parts[4].split(':').map(|item| item.parse::<i64>().ok()).collect::<Vec<_>>();
+    let [Some(hour), Some(minute), Some(second)] = clock[..] else {
+        return None;
+    };
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    let seconds = u64::try_from(seconds).ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+fn parse_retry_after(value: Option<&str>) -> Duration {
+    let fallback = Duration::from_secs(DEFAULT_RETRY_AFTER_SECS);
+    let Some(raw) = value.map(str::trim).filter(|item| !item.is_empty()) else {
+        return fallback;
+    };
+    let wait = if let Ok(seconds) = raw.parse::<u64>() {
+        Duration::from_secs(seconds)
+    } else if let Some(until) = parse_http_date(raw) {
+        until.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO)
+    } else {
+        fallback
+    };
+    wait.min(Duration::from_secs(MAX_RETRY_AFTER_SECS))
+}
+
+pub(crate) async fn acquire_request_slot(host: &str) -> Result<(), String> {
+    loop {
+        let wait = {
+            let mut guard =
+                host_limiters().lock().map_err(|_| "rate limiter is unavailable".to_string())?;
+            let now = Instant::now();
+            let limiter = guard.entry(host.to_string()).or_insert_with(|| HostLimiter::new(now));
+
+            if let Some(blocked_until) = limiter.blocked_until {
+                if blocked_until > now {
+                    return Err(format!(
+                        "rate limited by {}: retry in {}ms",
+                        host,
+                        blocked_until.duration_since(now).as_millis()
+                    ));
+                }
+                limiter.blocked_until = None;
+            }
+
+            limiter.refill(now);
+            if limiter.tokens >= 1.0 {
+                limiter.tokens -= 1.0;
+                return Ok(());
+            }
+            Duration::from_secs_f64((1.0 - limiter.tokens) / BUCKET_REFILL_PER_SEC)
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+pub(crate) fn record_rate_limited(host: &str, retry_after: Option<&str>) {
+    let Ok(mut guard) = host_limiters().lock() else {
+        return;
+    };
+    let now = Instant::now();
+    let until = now + parse_retry_after(retry_after);
+    let limiter = guard.entry(host.to_string()).or_insert_with(|| HostLimiter::new(now));
+    if limiter.blocked_until.is_none_or(|current| current < until) {
+        limiter.blocked_until = Some(until);
+    }
+}
+
+pub(crate) fn rate_limit_status() -> HashMap<String, u64> {
+    let Ok(guard) = host_limiters().lock() else {
+        return HashMap::new();
+    };
+    let now = Instant::now();
+    guard
+        .iter()
+        .map(|(host, limiter)| {
+            let remaining = limiter
+                .blocked_until
+                .map(|until| until.saturating_duration_since(now).as_millis() as u64)
+                .unwrap_or(0);
+            (host.clone(), remaining)
+        })
+        .collect()
+}
@@ -1,49 +1,183 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
 
+use crate::disk_cache::purge_disk_cache;
 use crate::http::{
-    error_response, perform_screeps_request, shared_http_client, ScreepsRequest, ScreepsResponse,
+    circuit_status, clear_request_log, clear_response_cache, configure_cache_ttls,
+    configure_client_timeouts, configure_response_limits, error_response, paginate,
+    perform_screeps_request, request_log_entries, response_cache_stats, set_path_prefix,
+    set_request_logging, set_user_agent, shared_http_client, CacheStats, CircuitStatus,
+    ClientTimeouts, RequestLogEntry, ResponseLimits, ScreepsRequest, ScreepsResponse,
 };
+use crate::rate_limit::rate_limit_status;
+
+static BATCH_CANCELLATIONS: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+
+const DEFAULT_PAGE_LIMIT: usize = 20;
+const DEFAULT_MAX_PAGES: usize = 10;
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ScreepsBatchRequest {
     requests: Vec<ScreepsRequest>,
     max_concurrency: Option<usize>,
+    correlation_id: Option<String>,
+    #[serde(default)]
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsPaginatedRequest {
+    request: ScreepsRequest,
+    items_key: String,
+    limit: Option<usize>,
+    max_pages: Option<usize>,
+    correlation_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScreepsBatchResultEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+    index: usize,
+    response: ScreepsResponse,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScreepsBatchDoneEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+    total: usize,
+    completed: usize,
+    cancelled: bool,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsCacheClearRequest {
+    base_url: Option<String>,
+    endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsPathPrefixRequest {
+    base_url: String,
+    prefix: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsTimeoutsRequest {
+    connect_ms: u64,
+    request_ms: u64,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsLimitsRequest {
+    max_response_bytes: Option<u64>,
+}
+
+fn batch_cancellations() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    BATCH_CANCELLATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+struct BatchCancellation {
+    correlation_id: Option<String>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl BatchCancellation {
+    fn register(correlation_id: Option<&str>) -> Self {
+        let correlation_id =
+            correlation_id.map(str::trim).filter(|value| !value.is_empty()).map(str::to_string);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        if let (Some(id), Ok(mut guard)) = (correlation_id.as_ref(), batch_cancellations().lock()) {
+            guard.insert(id.clone(), cancelled.clone());
+        }
+        Self { correlation_id, cancelled }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for BatchCancellation {
+    fn drop(&mut self) {
+        let Some(id) = self.correlation_id.as_ref() else {
+            return;
+        };
+        if let Ok(mut guard) = batch_cancellations().lock() {
+            // A newer batch may have reused the id; only remove our own registration.
+            if guard.get(id).is_some_and(|flag| Arc::ptr_eq(flag, &self.cancelled)) {
+                guard.remove(id);
+            }
+        }
+    }
 }
 
 #[tauri::command]
 pub async fn screeps_request(request: ScreepsRequest) -> Result<ScreepsResponse, String> {
     let client = shared_http_client()?;
-    perform_screeps_request(client, request).await
+    perform_screeps_request(&client, request).await
 }
 
 #[tauri::command]
 pub async fn screeps_request_many(
+    app: AppHandle,
     batch: ScreepsBatchRequest,
 ) -> Result<Vec<ScreepsResponse>, String> {
     let client = shared_http_client()?;
-    if batch.requests.is_empty() {
+    if batch.requests.is_empty() && !batch.stream {
         return Ok(Vec::new());
     }
 
+    let cancellation = BatchCancellation::register(batch.correlation_id.as_deref());
     let max_concurrency = batch.max_concurrency.unwrap_or(8).clamp(1, 32);
     let total = batch.requests.len();
     let mut output: Vec<Option<ScreepsResponse>> = (0..total).map(|_| None).collect();
-    let mut cursor = 0;
+    // Higher priorities run first; the sort is stable so equal priorities keep input order.
+    let mut execution_order = (0..total).collect::<Vec<usize>>();
+    execution_order
+        .sort_by_key(|&index| std::cmp::Reverse(batch.requests[index].priority.unwrap_or(0)));
 
-    while cursor < total {
-        let end = usize::min(cursor + max_concurrency, total);
-        let mut handles = Vec::with_capacity(end - cursor);
+    // Cancellation is checked between chunks, so requests already in flight still complete.
+    for chunk in execution_order.chunks(max_concurrency) {
+        if cancellation.is_cancelled() {
+            break;
+        }
+        let mut handles = Vec::with_capacity(chunk.len());
 
-        for index in cursor..end {
+        for &index in chunk {
             let request = batch.requests[index].clone();
             let request_for_error = request.clone();
             let task_client = client.clone();
+            let task_app = batch.stream.then(|| app.clone());
+            let correlation_id = batch.correlation_id.clone();
             let handle = tauri::async_runtime::spawn(async move {
                 let response = match perform_screeps_request(&task_client, request).await {
                     Ok(response) => response,
                     Err(error) => error_response(&request_for_error, error),
                 };
+                if let Some(app) = task_app {
+                    let _ = app.emit(
+                        "screeps://batch-result",
+                        ScreepsBatchResultEvent {
+                            correlation_id,
+                            index,
+                            response: response.clone(),
+                        },
+                    );
+                }
                 (index, response)
             });
             handles.push(handle);
@@ -54,15 +188,130 @@ pub async fn screeps_request_many(
                 handle.await.map_err(|error| format!("batch request task failed: {}", error))?;
             output[index] = Some(response);
         }
+    }
 
-        cursor = end;
+    if batch.stream {
+        let _ = app.emit(
+            "screeps://batch-done",
+            ScreepsBatchDoneEvent {
+                correlation_id: batch.correlation_id.clone(),
+                total,
+                completed: output.iter().filter(|response| response.is_some()).count(),
+                cancelled: cancellation.is_cancelled(),
+            },
+        );
+        return Ok(Vec::new());
     }
 
     output
         .into_iter()
         .enumerate()
-        .map(|(index, response)| {
-            response.ok_or_else(|| format!("batch response missing at index {}", index))
+        .map(|(index, response)| match response {
+            Some(response) => Ok(response),
+            None if cancellation.is_cancelled() => {
+                Ok(error_response(&batch.requests[index], "batch request cancelled".to_string()))
+            }
+            None => Err(format!("batch response missing at index {}", index)),
         })
         .collect()
 }
+
+#[tauri::command]
+pub async fn screeps_request_paginated(
+    request: ScreepsPaginatedRequest,
+) -> Result<Vec<Value>, String> {
+    let items_key = request.items_key.trim();
+    if items_key.is_empty() {
+        return Err("Items key cannot be empty".to_string());
+    }
+    let client = shared_http_client()?;
+    let cancellation = BatchCancellation::register(request.correlation_id.as_deref());
+    paginate(
+        &client,
+        request.request,
+        items_key,
+        request.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        request.max_pages.unwrap_or(DEFAULT_MAX_PAGES),
+        &cancellation.cancelled,
+    )
+    .await
+}
+
+#[tauri::command]
+pub fn screeps_rate_limit_status() -> HashMap<String, u64> {
+    rate_limit_status()
+}
+
+#[tauri::command]
+pub fn screeps_circuit_status() -> HashMap<String, CircuitStatus> {
+    circuit_status()
+}
+
+#[tauri::command]
+pub fn screeps_cache_clear(request: Option<ScreepsCacheClearRequest>) -> usize {
+    let request = request.unwrap_or_default();
+    clear_response_cache(request.base_url.as_deref(), request.endpoint.as_deref())
+}
+
+#[tauri::command]
+pub fn screeps_cache_stats() -> CacheStats {
+    response_cache_stats()
+}
+
+#[tauri::command]
+pub fn screeps_cache_configure(ttls: HashMap<String, u64>) -> HashMap<String, u64> {
+    configure_cache_ttls(ttls)
+}
+
+#[tauri::command]
+pub fn screeps_set_user_agent(user_agent: String) -> Result<String, String> {
+    set_user_agent(&user_agent)
+}
+
+#[tauri::command]
+pub fn screeps_set_path_prefix(request: ScreepsPathPrefixRequest) -> Result<String, String> {
+    set_path_prefix(&request.base_url, &request.prefix)
+}
+
+#[tauri::command]
+pub fn screeps_set_timeouts(request: ScreepsTimeoutsRequest) -> Result<ClientTimeouts, String> {
+    configure_client_timeouts(request.connect_ms, request.request_ms)
+}
+
+#[tauri::command]
+pub fn screeps_set_limits(request: Option<ScreepsLimitsRequest>) -> ResponseLimits {
+    configure_response_limits(request.unwrap_or_default().max_response_bytes)
+}
+
+#[tauri::command]
+pub fn screeps_set_logging(enabled: bool) -> bool {
+    set_request_logging(enabled)
+}
+
+#[tauri::command]
+pub fn screeps_request_log() -> Vec<RequestLogEntry> {
+    request_log_entries()
+}
+
+#[tauri::command]
+pub fn screeps_request_log_clear() -> usize {
+    clear_request_log()
+}
+
+#[tauri::command]
+pub fn screeps_cancel(correlation_id: String) -> bool {
+    let Some(cancelled) = batch_cancellations()
+        .lock()
+        .ok()
+        .and_then(|guard| guard.get(correlation_id.trim()).cloned())
+    else {
+        return false;
+    };
+    cancelled.store(true, Ordering::Relaxed);
+    true
+}
+
+#[tauri::command]
+pub fn screeps_cache_purge_disk() -> usize {
+    purge_disk_cache()
+}
@@ -0,0 +1,108 @@
+const RESOURCES: &[&str] = &[
+    "energy",
+    "power",
+    "ops",
+    "H",
+    "O",
+    "U",
+    "L",
+    "K",
+    "Z",
+    "X",
+    "G",
+    "OH",
+    "ZK",
+    "UL",
+    "UH",
+    "UO",
+    "KH",
+    "KO",
+    "LH",
+    "LO",
+    "ZH",
+    "ZO",
+    "GH",
+    "GO",
+    "UH2O",
+    "UHO2",
+    "KH2O",
+    "KHO2",
+    "LH2O",
+    "LHO2",
+    "ZH2O",
+    "ZHO2",
+    "GH2O",
+    "GHO2",
+    "XUH2O",
+    "XUHO2",
+    "XKH2O",
+    "XKHO2",
+    "XLH2O",
+    "XLHO2",
+    "XZH2O",
+    "XZHO2",
+    "XGH2O",
+    "XGHO2",
+    "silicon",
+    "metal",
+    "biomass",
+    "mist",
+    "utrium_bar",
+    "lemergium_bar",
+    "zynthium_bar",
+    "keanium_bar",
+    "ghodium_melt",
+    "oxidant",
+    "reductant",
+    "purifier",
+    "battery",
+    "composite",
+    "crystal",
+    "liquid",
+    "wire",
+    "switch",
+    "transistor",
+    "microchip",
+    "circuit",
+    "device",
+    "cell",
+    "phlegm",
+    "tissue",
+    "muscle",
+    "organoid",
+    "organism",
+    "alloy",
+    "tube",
+    "fixtures",
+    "frame",
+    "hydraulics",
+    "machine",
+    "condensate",
+    "concentrate",
+    "extract",
+    "spirit",
+    "emanation",
+    "essence",
+    "pixel",
+    "cpuUnlock",
+    "accessKey",
+];
+
+// Matching is case-insensitive; the canonical spelling is what the server expects.
+pub(crate) fn validate_resource(resource_type: &str) -> Result<String, String> {
+    let trimmed = resource_type.trim();
+    if trimmed.is_empty() {
+        return Err("Resource type cannot be empty".to_string());
+    }
+    RESOURCES
+        .iter()
+        .find(|known| **known == trimmed)
+        .or_else(|| RESOURCES.iter().find(|known| known.eq_ignore_ascii_case(trimmed)))
+        .map(|known| known.to_string())
+        .ok_or_else(|| format!("Unknown resource type \"{}\"", trimmed))
+}
+
+#[tauri::command]
+pub fn screeps_resource_list() -> Vec<String> {
+    RESOURCES.iter().map(|resource| resource.to_string()).collect()
+}
@@ -0,0 +1,277 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::http::normalize_base_url;
+use crate::rooms::{
+    fetch_room_detail, normalize_room_name, normalize_shard_for, RoomDetailSnapshot,
+    RoomObjectSummary, ScreepsRoomDetailRequest,
+};
+
+const MAX_STORED_BASELINES: usize = 64;
+
+static ROOM_BASELINES: OnceLock<Mutex<HashMap<String, StoredBaseline>>> = OnceLock::new();
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomBaselineClearRequest {
+    pub base_url: Option<String>,
+    pub room_name: Option<String>,
+    pub shard: Option<String>,
+}
+
+// Only the objects are needed, so full snapshots from the frontend deserialize as-is.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomDiffSnapshot {
+    #[serde(default)]
+    pub objects: Vec<RoomObjectSummary>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomDiffRequest {
+    pub before: RoomDiffSnapshot,
+    pub after: RoomDiffSnapshot,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomObjectMove {
+    pub id: String,
+    pub r#type: String,
+    pub from_x: i64,
+    pub from_y: i64,
+    pub to_x: i64,
+    pub to_y: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomObjectChange {
+    pub id: String,
+    pub r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits_before: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits_after: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits_delta: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store_delta: Option<HashMap<String, f64>>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomSnapshotDiff {
+    pub added: HashMap<String, RoomObjectSummary>,
+    pub removed: HashMap<String, RoomObjectSummary>,
+    pub moved: HashMap<String, RoomObjectMove>,
+    pub changed: HashMap<String, RoomObjectChange>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomBaselineDiffResponse {
+    pub room_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard: Option<String>,
+    pub baseline_fetched_at: String,
+    pub fetched_at: String,
+    pub diff: RoomSnapshotDiff,
+}
+
+#[derive(Debug, Clone)]
+struct StoredBaseline {
+    snapshot: RoomDetailSnapshot,
+    saved_at: Instant,
+}
+
+fn room_baselines() -> &'static Mutex<HashMap<String, StoredBaseline>> {
+    ROOM_BASELINES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn baseline_key(base_url: &str, shard: Option<&str>, room_name: &str) -> String {
+    format!("{}|{}|{}", normalize_base_url(base_url), shard.unwrap_or(""), room_name)
+}
+
+fn store_delta(
+    before: Option<&HashMap<String, f64>>,
+    after: Option<&HashMap<String, f64>>,
+) -> Option<HashMap<String, f64>> {
+    let empty = HashMap::new();
+    let before = before.unwrap_or(&empty);
+    let after = after.unwrap_or(&empty);
+    let mut delta = HashMap::new();
+    for (resource, amount) in after {
+        let change = amount - before.get(resource).copied().unwrap_or(0.0);
+        if change != 0.0 {
+            delta.insert(resource.clone(), change);
+        }
+    }
+    for (resource, amount) in before {
+        if !after.contains_key(resource) && *amount != 0.0 {
+            delta.insert(resource.clone(), -amount);
+        }
+    }
+    if delta.is_empty() {
+        None
+    } else {
+        Some(delta)
+    }
+}
+
+pub(crate) fn diff_room_snapshots(
+    before: &RoomDetailSnapshot,
+    after: &RoomDetailSnapshot,
+) -> RoomSnapshotDiff {
+    diff_room_objects(&before.objects, &after.objects)
+}
+
+fn diff_room_objects(
+    before: &[RoomObjectSummary],
+    after: &[RoomObjectSummary],
+) -> RoomSnapshotDiff {
+    let before_objects = before
+        .iter()
+        .map(|item| (item.id.clone(), item))
+        .collect::<HashMap<String, &RoomObjectSummary>>();
+    let after_objects = after
+        .iter()
+        .map(|item| (item.id.clone(), item))
+        .collect::<HashMap<String, &RoomObjectSummary>>();
+
+    let mut diff = RoomSnapshotDiff::default();
+    for (id, previous) in &before_objects {
+        if !after_objects.contains_key(id) {
+            diff.removed.insert(id.clone(), (*previous).clone());
+        }
+    }
+
+    for (id, current) in &after_objects {
+        let Some(previous) = before_objects.get(id) else {
+            diff.added.insert(id.clone(), (*current).clone());
+            continue;
+        };
+
+        if previous.x != current.x || previous.y != current.y {
+            diff.moved.insert(
+                id.clone(),
+                RoomObjectMove {
+                    id: id.clone(),
+                    r#type: current.r#type.clone(),
+                    from_x: previous.x,
+                    from_y: previous.y,
+                    to_x: current.x,
+                    to_y: current.y,
+                },
+            );
+        }
+
+        let hits_delta = match (previous.hits, current.hits) {
+            (Some(old_hits), Some(new_hits)) if old_hits != new_hits => Some(new_hits - old_hits),
+            _ => None,
+        };
+        let store_change = store_delta(previous.store.as_ref(), current.store.as_ref());
+        if hits_delta.is_some() || store_change.is_some() {
+            diff.changed.insert(
+                id.clone(),
+                RoomObjectChange {
+                    id: id.clone(),
+                    r#type: current.r#type.clone(),
+                    hits_before: previous.hits,
+                    hits_after: current.hits,
+                    hits_delta,
+                    store_delta: store_change,
+                },
+            );
+        }
+    }
+
+    diff
+}
+
+#[tauri::command]
+pub fn screeps_room_diff(request: ScreepsRoomDiffRequest) -> RoomSnapshotDiff {
+    diff_room_objects(&request.before.objects, &request.after.objects)
+}
+
+#[tauri::command]
+pub async fn screeps_room_baseline_save(
+    request: ScreepsRoomDetailRequest,
+) -> Result<RoomDetailSnapshot, String> {
+    let room_name = normalize_room_name(&request.room_name)?;
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let snapshot = fetch_room_detail(&request).await?;
+
+    let key = baseline_key(&request.base_url, shard.as_deref(), &room_name);
+    let mut guard =
+        room_baselines().lock().map_err(|_| "room baseline store is unavailable".to_string())?;
+    if !guard.contains_key(&key) && guard.len() >= MAX_STORED_BASELINES {
+        if let Some(oldest_key) =
+            guard.iter().min_by_key(|(_, entry)| entry.saved_at).map(|(key, _)| key.clone())
+        {
+            guard.remove(&oldest_key);
+        }
+    }
+    guard.insert(key, StoredBaseline { snapshot: snapshot.clone(), saved_at: Instant::now() });
+
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub async fn screeps_room_baseline_diff(
+    request: ScreepsRoomDetailRequest,
+) -> Result<RoomBaselineDiffResponse, String> {
+    let room_name = normalize_room_name(&request.room_name)?;
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let key = baseline_key(&request.base_url, shard.as_deref(), &room_name);
+    let baseline = room_baselines()
+        .lock()
+        .map_err(|_| "room baseline store is unavailable".to_string())?
+        .get(&key)
+        .map(|entry| entry.snapshot.clone())
+        .ok_or_else(|| format!("No baseline saved for room {}", room_name))?;
+
+    let current = fetch_room_detail(&request).await?;
+    let diff = diff_room_snapshots(&baseline, &current);
+
+    Ok(RoomBaselineDiffResponse {
+        room_name,
+        shard,
+        baseline_fetched_at: baseline.fetched_at,
+        fetched_at: current.fetched_at,
+        diff,
+    })
+}
+
+#[tauri::command]
+pub fn screeps_room_baseline_clear(
+    request: ScreepsRoomBaselineClearRequest,
+) -> Result<usize, String> {
+    let base_url = request.base_url.as_deref().map(normalize_base_url);
+    let room_name = match request.room_name.as_deref() {
+        Some(value) => Some(normalize_room_name(value)?),
+        None => None,
+    };
+    let shard = normalize_shard_for(
+        request.base_url.as_deref().unwrap_or_default(),
+        request.shard.as_deref(),
+    );
+
+    let mut guard =
+        room_baselines().lock().map_err(|_| "room baseline store is unavailable".to_string())?;
+    let before = guard.len();
+    guard.retain(|key, _| {
+        let mut parts = key.splitn(3, '|');
+        let key_base_url = parts.next().unwrap_or("");
+        let key_shard = parts.next().unwrap_or("");
+        let key_room = parts.next().unwrap_or("");
+        let matches = base_url.as_deref().is_none_or(|value| value == key_base_url)
+            && shard.as_deref().is_none_or(|value| value == key_shard)
+            && room_name.as_deref().is_none_or(|value| value == key_room);
+        !matches
+    });
+    Ok(before - guard.len())
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::rooms::{fetch_room_detail, RoomObjectSummary, ScreepsRoomDetailRequest};
+
+static EXPORT_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+const CSV_COLUMNS: [&str; 11] = [
+    "id",
+    "type",
+    "x",
+    "y",
+    "hits",
+    "hitsMax",
+    "owner",
+    "name",
+    "energy",
+    "energyCapacity",
+    "level",
+];
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomExportRequest {
+    pub room: Option<ScreepsRoomDetailRequest>,
+    pub snapshot: Option<Value>,
+    pub format: String,
+    pub path: String,
+}
+
+// CSV only needs the objects, so a snapshot handed back from the frontend deserializes as-is.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ExportSnapshotObjects {
+    #[serde(default)]
+    objects: Vec<RoomObjectSummary>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomExportResponse {
+    pub path: String,
+    pub bytes_written: u64,
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn optional_number(value: Option<f64>) -> String {
+    value.map(|number| number.to_string()).unwrap_or_default()
+}
+
+pub(crate) fn set_export_dir(dir: PathBuf) {
+    let _ = EXPORT_DIR.set(dir);
+}
+
+// Exports are confined to the app data directory; the caller only picks a relative file name.
+fn resolve_export_path(path: &str) -> Result<PathBuf, String> {
+    let dir = EXPORT_DIR.get().ok_or_else(|| "export directory is unavailable".to_string())?;
+    let relative = Path::new(path);
+    if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(format!("Export path must be a relative file name: {}", path));
+    }
+    Ok(dir.join(relative))
+}
+
+fn objects_to_csv(objects: &[RoomObjectSummary]) -> String {
+    let mut output = CSV_COLUMNS.join(",");
+    output.push('\n');
+    for object in objects {
+        let row = [
+            object.id.clone(),
+            object.r#type.clone(),
+            object.x.to_string(),
+            object.y.to_string(),
+            optional_number(object.hits),
+            optional_number(object.hits_max),
+            object.owner.clone().or_else(|| object.user.clone()).unwrap_or_default(),
+            object.name.clone().unwrap_or_default(),
+            optional_number(object.energy),
+            optional_number(object.energy_capacity),
+            optional_number(object.level),
+        ];
+        output.push_str(&row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+        output.push('\n');
+    }
+    output
+}
+
+#[tauri::command]
+pub async fn screeps_room_export(
+    request: ScreepsRoomExportRequest,
+) -> Result<ScreepsRoomExportResponse, String> {
+    let path = request.path.trim();
+    if path.is_empty() {
+        return Err("Export path cannot be empty".to_string());
+    }
+    let format = request.format.trim().to_ascii_lowercase();
+    if format != "json" && format != "csv" {
+        return Err(format!("Unsupported export format: {}", request.format));
+    }
+
+    let path = resolve_export_path(path)?;
+    let snapshot = match (request.snapshot, request.room.as_ref()) {
+        (Some(snapshot), _) => snapshot,
+        (None, Some(room)) => serde_json::to_value(fetch_room_detail(room).await?)
+            .map_err(|error| format!("failed to serialize room snapshot: {}", error))?,
+        (None, None) => return Err("Either a snapshot or a room request is required".to_string()),
+    };
+    let contents = if format == "csv" {
+        let parsed = serde_json::from_value::<ExportSnapshotObjects>(snapshot)
+            .map_err(|error| format!("invalid room snapshot: {}", error))?;
+        objects_to_csv(&parsed.objects)
+    } else {
+        serde_json::to_string_pretty(&snapshot)
+            .map_err(|error| format!("failed to serialize room snapshot: {}", error))?
+    };
+
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create export directory: {}", error))?;
+    }
+    fs::write(&path, contents.as_bytes())
+        .map_err(|error| format!("failed to write room export: {}", error))?;
+
+    Ok(ScreepsRoomExportResponse {
+        path: path.to_string_lossy().to_string(),
+        bytes_written: contents.len() as u64,
+    })
+}
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest};
+use crate::rooms::{normalize_room_name, normalize_shard_for};
+
+const DEFAULT_HISTORY_CHUNK_SIZE: u64 = 20;
+const DEFAULT_HISTORY_SHARD: &str = "shard0";
+const MAX_HISTORY_TICKS: u64 = 500;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomHistoryRequest {
+    pub base_url: String,
+    pub token: Option<String>,
+    pub username: Option<String>,
+    pub room: String,
+    pub shard: Option<String>,
+    pub start_tick: u64,
+    pub tick_count: u64,
+    pub chunk_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomHistoryObject {
+    pub id: String,
+    pub r#type: String,
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomHistoryFrame {
+    pub tick: u64,
+    pub objects: Vec<RoomHistoryObject>,
+}
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn value_as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => number.as_i64().or_else(|| number.as_f64().map(|raw| raw as i64)),
+        Value::String(text) => text.trim().parse::<i64>().ok(),
+        _ => None,
+    }
+}
+
+// History ticks after the first one in a chunk only carry changed fields; null removes an object.
+fn apply_history_patch(target: &mut Map<String, Value>, patch: &Map<String, Value>) {
+    for (key, value) in patch {
+        match (target.get_mut(key), value) {
+            (_, Value::Null) => {
+                target.remove(key);
+            }
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                apply_history_patch(existing, nested);
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn snapshot_frame(tick: u64, state: &Map<String, Value>) -> RoomHistoryFrame {
+    let mut objects = state
+        .iter()
+        .filter_map(|(id, object)| {
+            Some(RoomHistoryObject {
+                id: id.clone(),
+                r#type: object.get("type").and_then(value_as_non_empty_string)?,
+                x: object.get("x").and_then(value_as_i64)?,
+                y: object.get("y").and_then(value_as_i64)?,
+                owner: object.get("user").and_then(value_as_non_empty_string),
+            })
+        })
+        .collect::<Vec<RoomHistoryObject>>();
+    objects.sort_by(|left, right| left.id.cmp(&right.id));
+    RoomHistoryFrame { tick, objects }
+}
+
+fn parse_history_chunk(payload: &Value, from_tick: u64, to_tick: u64) -> Vec<RoomHistoryFrame> {
+    let Some(ticks) = payload.get("ticks").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut ordered = ticks
+        .iter()
+        .filter_map(|(tick, objects)| Some((tick.trim().parse::<u64>().ok()?, objects)))
+        .collect::<Vec<(u64, &Value)>>();
+    ordered.sort_by_key(|(tick, _)| *tick);
+
+    let mut state = Map::new();
+    let mut frames = Vec::new();
+    for (tick, objects) in ordered {
+        if let Some(objects) = objects.as_object() {
+            apply_history_patch(&mut state, objects);
+        }
+        if tick >= from_tick && tick < to_tick {
+            frames.push(snapshot_frame(tick, &state));
+        }
+    }
+    frames
+}
+
+#[tauri::command]
+pub async fn screeps_room_history_fetch(
+    request: ScreepsRoomHistoryRequest,
+) -> Result<Vec<RoomHistoryFrame>, String> {
+    let room = normalize_room_name(&request.room)?;
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref())
+        .unwrap_or_else(|| DEFAULT_HISTORY_SHARD.to_string());
+    let chunk_size = request.chunk_size.unwrap_or(DEFAULT_HISTORY_CHUNK_SIZE).max(1);
+    let tick_count = request.tick_count.min(MAX_HISTORY_TICKS);
+    if tick_count == 0 {
+        return Ok(Vec::new());
+    }
+    let end_tick = request.start_tick.saturating_add(tick_count);
+    let first_chunk = request.start_tick - request.start_tick % chunk_size;
+
+    let client = shared_http_client()?;
+    let mut frames = Vec::new();
+    let mut chunk_tick = first_chunk;
+    while chunk_tick < end_tick {
+        let response = perform_screeps_request(
+            &client,
+            ScreepsRequest {
+                base_url: request.base_url.clone(),
+                endpoint: format!("/room-history/{}/{}/{}.json", shard, room, chunk_tick),
+                method: Some("GET".to_string()),
+                token: request.token.clone(),
+                username: request.username.clone(),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        // Chunks that are not written yet or have expired are left as a gap in the replay.
+        if response.ok {
+            frames.extend(parse_history_chunk(&response.data, request.start_tick, end_tick));
+        } else if response.status != 404 {
+            return Err(format!("room history request failed: HTTP {}", response.status));
+        }
+        chunk_tick += chunk_size;
+    }
+
+    Ok(frames)
+}
@@ -1,9 +1,37 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
 
+use crate::activity::{fetch_nukes, NukeEntry, ScreepsNukesRequest};
+use crate::game_time::{fetch_game_time, ScreepsGameTimeRequest};
 use crate::http::{perform_screeps_request, shared_http_client, ScreepsRequest};
+use crate::shards::{is_single_shard_server, known_shard_name};
+
+const ROOM_OVERVIEW_INTERVAL: u32 = 8;
+const BODY_PART_COSTS: [(&str, f64); 8] = [
+    ("move", 50.0),
+    ("work", 100.0),
+    ("carry", 50.0),
+    ("attack", 80.0),
+    ("ranged_attack", 150.0),
+    ("heal", 250.0),
+    ("claim", 600.0),
+    ("tough", 10.0),
+];
+const ATTACK_POWER: f64 = 30.0;
+const RANGED_ATTACK_POWER: f64 = 10.0;
+const HEAL_POWER: f64 = 12.0;
+const HARVEST_POWER: f64 = 2.0;
+const SOURCE_KEEPER_USER_ID: &str = "3";
+const DEFAULT_DAMAGE_THRESHOLD: f64 = 0.5;
+const HIGH_THREAT_PARTS: f64 = 15.0;
+const CRITICAL_THREAT_PARTS: f64 = 50.0;
+const NUKER_ENERGY_CAPACITY: f64 = 300_000.0;
+const NUKER_GHODIUM_CAPACITY: f64 = 5_000.0;
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +51,140 @@ pub struct ScreepsRoomDetailRequest {
     pub room_name: String,
     pub shard: Option<String>,
     pub rooms_endpoint: Option<ScreepsRoomEndpointConfig>,
+    pub decode_terrain: Option<bool>,
+    pub include_history: Option<bool>,
+    pub render_frame: Option<bool>,
+    pub self_user_id: Option<String>,
+    pub damage_threshold: Option<f64>,
+    pub since_game_time: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomsDetailBatchRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub room_names: Vec<String>,
+    pub shard: Option<String>,
+    pub rooms_endpoint: Option<ScreepsRoomEndpointConfig>,
+    pub decode_terrain: Option<bool>,
+    pub include_history: Option<bool>,
+    pub render_frame: Option<bool>,
+    pub self_user_id: Option<String>,
+    pub damage_threshold: Option<f64>,
+    pub max_concurrency: Option<usize>,
+    pub correlation_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScreepsRoomsProgressEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+    room_name: String,
+    ok: bool,
+    completed: usize,
+    total: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScreepsRoomsDoneEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+    completed: usize,
+    total: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsEmpireResourcesRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub room_names: Vec<String>,
+    pub shard: Option<String>,
+    pub max_concurrency: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpireResourcesSummary {
+    pub rooms: HashMap<String, HashMap<String, f64>>,
+    pub totals: HashMap<String, f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomDefenseRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+    pub room_name: String,
+    pub shard: Option<String>,
+    pub self_user_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum ThreatLevel {
+    None,
+    Low,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum RoomVisibility {
+    Visible,
+    Invisible,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DefenseTowerSummary {
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy_capacity: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreatBreakdown {
+    pub attack: f64,
+    pub ranged_attack: f64,
+    pub heal: f64,
+    pub parts: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomDefenseReport {
+    pub room_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_time: Option<f64>,
+    pub towers: Vec<DefenseTowerSummary>,
+    pub barriers: Vec<RoomStructureSummary>,
+    pub hostiles: Vec<RoomCreepSummary>,
+    pub threat: ThreatBreakdown,
+    pub safe_mode_active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_mode_until: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_mode_available: Option<f64>,
+    pub incoming_nukes: Vec<NukeEntry>,
+    pub threat_level: ThreatLevel,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -39,6 +201,53 @@ pub struct RoomMineralSummary {
     pub r#type: Option<String>,
     pub x: i64,
     pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mineral_amount: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub density: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regeneration_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticks_to_regeneration: Option<f64>,
+    pub depleted: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositSummary {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit_type: Option<String>,
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_cooldown: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticks_to_decay: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerBankSummary {
+    pub id: String,
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits_max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticks_remaining: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -51,6 +260,115 @@ pub struct RoomStructureSummary {
     pub hits: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hits_max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<StructureResourceSummary>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LabSummary {
+    pub id: String,
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mineral_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mineral_amount: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<f64>,
+    #[serde(skip)]
+    cooldown_time: Option<f64>,
+    pub reaction_ready: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerCreepPowerSummary {
+    pub power: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<f64>,
+    #[serde(skip)]
+    cooldown_time: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerCreepSummary {
+    pub name: String,
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<f64>,
+    pub powers: Vec<PowerCreepPowerSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnProgressSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creep_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub need_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_pct: Option<f64>,
+    #[serde(skip)]
+    spawn_time: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy_capacity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spawning: Option<SpawnProgressSummary>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NukerSummary {
+    pub energy: f64,
+    pub ghodium: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<f64>,
+    #[serde(skip)]
+    cooldown_time: Option<f64>,
+    #[serde(skip)]
+    energy_capacity: f64,
+    #[serde(skip)]
+    ghodium_capacity: f64,
+    pub ready: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructureResourceSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy_capacity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mineral_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mineral_amount: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mineral_capacity: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -63,16 +381,48 @@ pub struct RoomCreepSummary {
     pub y: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ttl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_stats: Option<BodyStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disposition: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyStats {
+    pub parts: HashMap<String, u32>,
+    pub total_parts: u32,
+    pub energy_cost: f64,
+    pub attack: f64,
+    pub ranged_attack: f64,
+    pub heal: f64,
+    pub harvest: f64,
+    pub boosted: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
+pub struct ConstructionSiteSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    pub x: i64,
+    pub y: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_total: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct RoomObjectActionTarget {
     pub x: f64,
     pub y: f64,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RoomObjectSpawningSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -81,7 +431,7 @@ pub struct RoomObjectSpawningSummary {
     pub spawn_time: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RoomObjectBodyPartSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -92,7 +442,7 @@ pub struct RoomObjectBodyPartSummary {
     pub boost: Option<String>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RoomObjectSaySummary {
     pub text: String,
@@ -102,6 +452,26 @@ pub struct RoomObjectSaySummary {
 
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
+pub struct RoomCoords {
+    pub wx: i64,
+    pub wy: i64,
+    pub sector: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ControllerSignSummary {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct RoomObjectReservationSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
@@ -113,7 +483,7 @@ pub struct RoomObjectReservationSummary {
     pub ticks_to_end: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RoomObjectSummary {
     pub id: String,
@@ -162,9 +532,46 @@ pub struct RoomObjectSummary {
 
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
+pub struct RoomHistoryPoint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<f64>,
+    pub value: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomHistorySeries {
+    pub interval: u32,
+    pub series: HashMap<String, Vec<RoomHistoryPoint>>,
+    pub maxima: HashMap<String, f64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Tile {
+    pub x: i64,
+    pub y: i64,
+    pub terrain: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub object_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomRenderFrame {
+    pub tiles: Vec<Tile>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct RoomDetailSnapshot {
     pub fetched_at: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub not_modified: bool,
     pub room_name: String,
+    pub visibility: RoomVisibility,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_coords: Option<RoomCoords>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shard: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -172,18 +579,63 @@ pub struct RoomDetailSnapshot {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub controller_level: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller_downgrade_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller_ticks_to_downgrade: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_mode_until: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safe_mode_available: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller_reservation: Option<RoomObjectReservationSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub controller_sign: Option<ControllerSignSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub energy_available: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub energy_capacity: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub terrain_encoded: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub terrain_grid: Option<Vec<Vec<u8>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub render_frame: Option<RoomRenderFrame>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub game_time: Option<f64>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub resource_totals: HashMap<String, f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<RoomHistorySeries>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub data_sources: HashMap<String, bool>,
     pub sources: Vec<RoomSourceSummary>,
     pub minerals: Vec<RoomMineralSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deposits: Vec<DepositSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub power_banks: Vec<PowerBankSummary>,
     pub structures: Vec<RoomStructureSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub damaged_structures: Vec<RoomStructureSummary>,
     pub creeps: Vec<RoomCreepSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub power_creeps: Vec<PowerCreepSummary>,
+    pub construction_sites: Vec<ConstructionSiteSummary>,
     pub objects: Vec<RoomObjectSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub spawns: Vec<SpawnSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labs: Vec<LabSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nuker: Option<NukerSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hostiles: Vec<RoomCreepSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threat_score: Option<f64>,
+    #[serde(skip)]
+    pub self_user_id: Option<String>,
+    #[serde(skip)]
+    pub damage_threshold: Option<f64>,
 }
 
 #[derive(Debug, Default)]
@@ -191,13 +643,28 @@ struct ParsedEntities {
     shard: Option<String>,
     owner: Option<String>,
     controller_level: Option<f64>,
+    controller_downgrade_time: Option<f64>,
+    controller_ticks_to_downgrade: Option<f64>,
+    safe_mode_until: Option<f64>,
+    safe_mode_available: Option<f64>,
+    controller_reservation: Option<RoomObjectReservationSummary>,
+    controller_sign: Option<ControllerSignSummary>,
     energy_available: Option<f64>,
     energy_capacity: Option<f64>,
+    resource_totals: HashMap<String, f64>,
     sources: Vec<RoomSourceSummary>,
     minerals: Vec<RoomMineralSummary>,
+    deposits: Vec<DepositSummary>,
+    power_banks: Vec<PowerBankSummary>,
     structures: Vec<RoomStructureSummary>,
+    damaged_structures: Vec<RoomStructureSummary>,
     creeps: Vec<RoomCreepSummary>,
+    power_creeps: Vec<PowerCreepSummary>,
+    construction_sites: Vec<ConstructionSiteSummary>,
     objects: Vec<RoomObjectSummary>,
+    spawns: Vec<SpawnSummary>,
+    labs: Vec<LabSummary>,
+    nuker: Option<NukerSummary>,
 }
 
 fn as_object(value: &Value) -> Option<&Map<String, Value>> {
@@ -275,7 +742,7 @@ fn map_first_f64(map: &Map<String, Value>, keys: &[&str]) -> Option<f64> {
     None
 }
 
-fn normalize_shard(shard_input: Option<&str>) -> Option<String> {
+pub(crate) fn normalize_shard(shard_input: Option<&str>) -> Option<String> {
     let shard = shard_input?.trim().to_ascii_lowercase();
     if !shard.starts_with("shard") {
         return None;
@@ -287,6 +754,14 @@ fn normalize_shard(shard_input: Option<&str>) -> Option<String> {
     Some(shard)
 }
 
+pub(crate) fn normalize_shard_for(base_url: &str, shard_input: Option<&str>) -> Option<String> {
+    let shard = shard_input?.trim();
+    if shard.is_empty() {
+        return None;
+    }
+    known_shard_name(base_url, shard).or_else(|| normalize_shard(Some(shard)))
+}
+
 fn extract_room_candidate(value: &str) -> Option<String> {
     let upper = value.to_ascii_uppercase();
     let chars = upper.chars().collect::<Vec<char>>();
@@ -320,7 +795,39 @@ fn extract_room_candidate(value: &str) -> Option<String> {
     None
 }
 
-fn normalize_room_name(room_name: &str) -> Result<String, String> {
+// World coordinates: E0/S0 map to 0, W0/N0 map to -1, growing outwards.
+pub(crate) fn room_name_to_xy(room_name: &str) -> Option<(i64, i64)> {
+    let candidate = extract_room_candidate(room_name.trim())?;
+    let vertical_at = candidate.find(['N', 'S'])?;
+    let (horizontal, vertical) = candidate.split_at(vertical_at);
+    let horizontal_value = horizontal[1..].parse::<i64>().ok()?;
+    let vertical_value = vertical[1..].parse::<i64>().ok()?;
+    let x = if horizontal.starts_with('W') { -horizontal_value - 1 } else { horizontal_value };
+    let y = if vertical.starts_with('N') { -vertical_value - 1 } else { vertical_value };
+    Some((x, y))
+}
+
+pub(crate) fn room_xy_to_name(x: i64, y: i64) -> String {
+    let horizontal = if x < 0 { format!("W{}", -x - 1) } else { format!("E{}", x) };
+    let vertical = if y < 0 { format!("N{}", -y - 1) } else { format!("S{}", y) };
+    format!("{}{}", horizontal, vertical)
+}
+
+// Sectors are named after their highway corner, so W5N3 sits in sector W0N0.
+fn sector_axis(value: i64) -> i64 {
+    if value < 0 {
+        -((-value - 1) / 10 * 10) - 1
+    } else {
+        value / 10 * 10
+    }
+}
+
+fn room_coords(room_name: &str) -> Option<RoomCoords> {
+    let (wx, wy) = room_name_to_xy(room_name)?;
+    Some(RoomCoords { wx, wy, sector: room_xy_to_name(sector_axis(wx), sector_axis(wy)) })
+}
+
+pub(crate) fn normalize_room_name(room_name: &str) -> Result<String, String> {
     let normalized = room_name.trim().to_ascii_uppercase();
     if extract_room_candidate(&normalized).as_deref() != Some(normalized.as_str()) {
         return Err(format!("Invalid room name: {}", room_name));
@@ -404,8 +911,64 @@ fn parse_body(value: Option<&Value>) -> Option<Vec<RoomObjectBodyPartSummary>> {
     }
 }
 
-fn parse_say(value: Option<&Value>) -> Option<RoomObjectSaySummary> {
-    let raw = value?;
+fn boost_multiplier(part_type: &str, boost: Option<&str>) -> f64 {
+    let Some(boost) = boost else {
+        return 1.0;
+    };
+    match (part_type, boost) {
+        ("attack", "UH") | ("ranged_attack", "KO") | ("heal", "LO") => 2.0,
+        ("attack", "UH2O") | ("ranged_attack", "KHO2") | ("heal", "LHO2") => 3.0,
+        ("attack", "XUH2O") | ("ranged_attack", "XKHO2") | ("heal", "XLHO2") => 4.0,
+        ("work", "UO") => 3.0,
+        ("work", "UHO2") => 5.0,
+        ("work", "XUHO2") => 7.0,
+        _ => 1.0,
+    }
+}
+
+fn compute_body_stats(body: &[RoomObjectBodyPartSummary]) -> BodyStats {
+    let mut stats = BodyStats {
+        parts: HashMap::new(),
+        total_parts: 0,
+        energy_cost: 0.0,
+        attack: 0.0,
+        ranged_attack: 0.0,
+        heal: 0.0,
+        harvest: 0.0,
+        boosted: false,
+    };
+    for part in body {
+        let Some(part_type) = part.r#type.as_deref() else {
+            continue;
+        };
+        let part_type = part_type.to_ascii_lowercase();
+        *stats.parts.entry(part_type.clone()).or_insert(0) += 1;
+        stats.total_parts += 1;
+        stats.energy_cost += BODY_PART_COSTS
+            .iter()
+            .find(|(name, _)| *name == part_type)
+            .map(|(_, cost)| *cost)
+            .unwrap_or(0.0);
+        stats.boosted |= part.boost.is_some();
+
+        // Destroyed parts keep their cost but no longer contribute output.
+        if part.hits.is_some_and(|hits| hits <= 0.0) {
+            continue;
+        }
+        let multiplier = boost_multiplier(&part_type, part.boost.as_deref());
+        match part_type.as_str() {
+            "attack" => stats.attack += ATTACK_POWER * multiplier,
+            "ranged_attack" => stats.ranged_attack += RANGED_ATTACK_POWER * multiplier,
+            "heal" => stats.heal += HEAL_POWER * multiplier,
+            "work" => stats.harvest += HARVEST_POWER * multiplier,
+            _ => {}
+        }
+    }
+    stats
+}
+
+fn parse_say(value: Option<&Value>) -> Option<RoomObjectSaySummary> {
+    let raw = value?;
     if let Some(text) = value_as_non_empty_string(raw) {
         return Some(RoomObjectSaySummary { text, is_public: None });
     }
@@ -427,6 +990,16 @@ fn parse_reservation(value: Option<&Value>) -> Option<RoomObjectReservationSumma
     Some(RoomObjectReservationSummary { username, user, end_time, ticks_to_end })
 }
 
+fn parse_controller_sign(value: Option<&Value>) -> Option<ControllerSignSummary> {
+    let record = value.and_then(as_object)?;
+    Some(ControllerSignSummary {
+        text: map_first_string(record, &["text"])?,
+        username: map_first_string(record, &["username"]),
+        user: map_first_string(record, &["user", "userId"]),
+        time: map_first_f64(record, &["time"]),
+    })
+}
+
 fn parse_spawning(value: Option<&Value>) -> Option<RoomObjectSpawningSummary> {
     let record = value.and_then(as_object)?;
     let need_time = map_first_f64(record, &["needTime", "remainingTime"]);
@@ -437,6 +1010,23 @@ fn parse_spawning(value: Option<&Value>) -> Option<RoomObjectSpawningSummary> {
     Some(RoomObjectSpawningSummary { need_time, spawn_time })
 }
 
+fn parse_spawn_progress(value: Option<&Value>) -> Option<SpawnProgressSummary> {
+    let record = value.and_then(as_object)?;
+    let need_time = map_first_f64(record, &["needTime"]);
+    let remaining_time = map_first_f64(record, &["remainingTime"]);
+    let spawn_time = map_first_f64(record, &["spawnTime", "endTime"]);
+    if need_time.is_none() && remaining_time.is_none() && spawn_time.is_none() {
+        return None;
+    }
+    Some(SpawnProgressSummary {
+        creep_name: map_first_string(record, &["name", "creepName"]),
+        need_time,
+        remaining_time,
+        progress_pct: None,
+        spawn_time,
+    })
+}
+
 fn parse_action_log(value: Option<&Value>) -> Option<HashMap<String, RoomObjectActionTarget>> {
     let record = value.and_then(as_object)?;
     let mut out = HashMap::new();
@@ -475,6 +1065,84 @@ fn parse_action_log(value: Option<&Value>) -> Option<HashMap<String, RoomObjectA
     }
 }
 
+fn parse_structure_resources(
+    object_type: &str,
+    record: &Map<String, Value>,
+    store: Option<&HashMap<String, f64>>,
+) -> Option<StructureResourceSummary> {
+    let capacities = collect_numeric_map(record.get("storeCapacityResource"));
+    let stored = |resource: &str| store.and_then(|item| item.get(resource).copied());
+    let capacity_of =
+        |resource: &str| capacities.as_ref().and_then(|item| item.get(resource).copied());
+
+    let energy = map_first_f64(record, &["energy"]).or_else(|| stored("energy"));
+    let energy_capacity = map_first_f64(record, &["energyCapacity"])
+        .or_else(|| capacity_of("energy"))
+        .or_else(|| map_first_f64(record, &["storeCapacity"]));
+
+    let mineral_type = match object_type {
+        "tower" | "link" | "spawn" | "extension" => None,
+        "nuker" => Some("G".to_string()),
+        "powerSpawn" => Some("power".to_string()),
+        "lab" => map_first_string(record, &["mineralType"]).or_else(|| {
+            store?
+                .iter()
+                .find(|(key, amount)| *key != "energy" && **amount > 0.0)
+                .map(|(key, _)| key.clone())
+        }),
+        _ => return None,
+    };
+    let mineral_amount = match (object_type, mineral_type.as_deref()) {
+        ("lab", Some(kind)) => map_first_f64(record, &["mineralAmount"]).or_else(|| stored(kind)),
+        ("nuker", Some(kind)) => map_first_f64(record, &["G"]).or_else(|| stored(kind)),
+        ("powerSpawn", Some(kind)) => map_first_f64(record, &["power"]).or_else(|| stored(kind)),
+        _ => None,
+    };
+    let mineral_capacity = match (object_type, mineral_type.as_deref()) {
+        ("lab", Some(kind)) => {
+            map_first_f64(record, &["mineralCapacity"]).or_else(|| capacity_of(kind))
+        }
+        ("nuker", Some(kind)) => {
+            map_first_f64(record, &["GCapacity"]).or_else(|| capacity_of(kind))
+        }
+        ("powerSpawn", Some(kind)) => {
+            map_first_f64(record, &["powerCapacity"]).or_else(|| capacity_of(kind))
+        }
+        _ => None,
+    };
+
+    if energy.is_none() && energy_capacity.is_none() && mineral_amount.is_none() {
+        return None;
+    }
+    Some(StructureResourceSummary {
+        energy,
+        energy_capacity,
+        mineral_type,
+        mineral_amount,
+        mineral_capacity,
+    })
+}
+
+fn parse_powers(value: Option<&Value>) -> Vec<PowerCreepPowerSummary> {
+    let Some(record) = value.and_then(as_object) else {
+        return Vec::new();
+    };
+    let mut powers = record
+        .iter()
+        .filter_map(|(key, entry)| {
+            let entry = as_object(entry);
+            Some(PowerCreepPowerSummary {
+                power: key.trim().parse::<u32>().ok()?,
+                level: entry.and_then(|item| map_first_f64(item, &["level"])),
+                cooldown: entry.and_then(|item| map_first_f64(item, &["cooldown"])),
+                cooldown_time: entry.and_then(|item| map_first_f64(item, &["cooldownTime"])),
+            })
+        })
+        .collect::<Vec<PowerCreepPowerSummary>>();
+    powers.sort_by_key(|item| item.power);
+    powers
+}
+
 fn is_structure_type(kind: &str) -> bool {
     matches!(
         kind,
@@ -593,15 +1261,30 @@ fn parse_entities(
     room_name: &str,
     shard_hint: Option<String>,
     payloads: &[Option<&Value>],
+    damage_threshold: f64,
 ) -> ParsedEntities {
     let mut sources = HashMap::<String, RoomSourceSummary>::new();
     let mut minerals = HashMap::<String, RoomMineralSummary>::new();
+    let mut deposits = HashMap::<String, DepositSummary>::new();
+    let mut power_banks = HashMap::<String, PowerBankSummary>::new();
     let mut structures = HashMap::<String, RoomStructureSummary>::new();
+    let mut damaged_structures = HashMap::<String, RoomStructureSummary>::new();
     let mut creeps = HashMap::<String, RoomCreepSummary>::new();
+    let mut power_creeps = HashMap::<String, PowerCreepSummary>::new();
+    let mut construction_sites = HashMap::<String, ConstructionSiteSummary>::new();
     let mut objects = HashMap::<String, RoomObjectSummary>::new();
+    let mut spawns = HashMap::<String, SpawnSummary>::new();
+    let mut labs = HashMap::<String, LabSummary>::new();
+    let mut nuker = None;
 
     let mut owner = None;
     let mut controller_level = None;
+    let mut controller_downgrade_time = None;
+    let mut controller_ticks_to_downgrade = None;
+    let mut safe_mode_until = None;
+    let mut safe_mode_available = None;
+    let mut controller_reservation = None;
+    let mut controller_sign = None;
     let mut energy_available: Option<f64> = None;
     let mut energy_capacity: Option<f64> = None;
     let mut shard = shard_hint;
@@ -643,6 +1326,19 @@ fn parse_entities(
             let object_energy = map_first_f64(&record, &["energy"])
                 .or_else(|| store.as_ref().and_then(|item| item.get("energy").copied()));
             let object_energy_capacity = map_first_f64(&record, &["energyCapacity"]);
+            let structure_resources = if is_structure_type(&object_type) {
+                parse_structure_resources(&object_type, &record, store.as_ref())
+            } else {
+                None
+            };
+
+            let object_body = parse_body(
+                record
+                    .get("body")
+                    .or_else(|| record.get("bodyParts"))
+                    .or_else(|| record.get("parts")),
+            );
+            let body_stats = object_body.as_deref().map(compute_body_stats);
 
             let object_summary = RoomObjectSummary {
                 id: object_id.clone(),
@@ -662,12 +1358,7 @@ fn parse_entities(
                 progress: record.get("progress").and_then(value_as_f64),
                 progress_total: map_first_f64(&record, &["progressTotal", "total"]),
                 mineral_type: map_first_string(&record, &["mineralType"]),
-                body: parse_body(
-                    record
-                        .get("body")
-                        .or_else(|| record.get("bodyParts"))
-                        .or_else(|| record.get("parts")),
-                ),
+                body: object_body,
                 say: parse_say(record.get("say").or_else(|| record.get("message"))),
                 reservation: parse_reservation(record.get("reservation")),
                 spawning: parse_spawning(record.get("spawning")),
@@ -689,7 +1380,113 @@ fn parse_entities(
                 continue;
             }
 
-            if object_type == "mineral" || map_first_string(&record, &["mineralType"]).is_some() {
+            if object_type == "constructionSite" {
+                construction_sites.insert(
+                    format!("{}:{}", x, y),
+                    ConstructionSiteSummary {
+                        r#type: map_first_string(&record, &["structureType"]),
+                        x,
+                        y,
+                        progress: record.get("progress").and_then(value_as_f64),
+                        progress_total: map_first_f64(&record, &["progressTotal", "total"]),
+                    },
+                );
+                continue;
+            }
+
+            if object_type == "spawn" {
+                spawns.insert(
+                    object_id.clone(),
+                    SpawnSummary {
+                        name: object_name.clone(),
+                        x,
+                        y,
+                        energy: object_energy,
+                        energy_capacity: object_energy_capacity
+                            .or_else(|| structure_resources.as_ref()?.energy_capacity),
+                        spawning: parse_spawn_progress(record.get("spawning")),
+                    },
+                );
+            }
+
+            if object_type == "lab" {
+                let resources = structure_resources.as_ref();
+                labs.insert(
+                    object_id.clone(),
+                    LabSummary {
+                        id: object_id.clone(),
+                        x,
+                        y,
+                        mineral_type: resources.and_then(|item| item.mineral_type.clone()),
+                        mineral_amount: resources.and_then(|item| item.mineral_amount),
+                        energy: resources.and_then(|item| item.energy),
+                        cooldown: map_first_f64(&record, &["cooldown"]),
+                        cooldown_time: map_first_f64(&record, &["cooldownTime"]),
+                        reaction_ready: false,
+                    },
+                );
+            }
+
+            if object_type == "nuker" && nuker.is_none() {
+                let resources = structure_resources.as_ref();
+                nuker = Some(NukerSummary {
+                    energy: resources.and_then(|item| item.energy).unwrap_or(0.0),
+                    ghodium: resources.and_then(|item| item.mineral_amount).unwrap_or(0.0),
+                    cooldown: map_first_f64(&record, &["cooldown"]),
+                    cooldown_time: map_first_f64(&record, &["cooldownTime"]),
+                    energy_capacity: resources
+                        .and_then(|item| item.energy_capacity)
+                        .unwrap_or(NUKER_ENERGY_CAPACITY),
+                    ghodium_capacity: resources
+                        .and_then(|item| item.mineral_capacity)
+                        .unwrap_or(NUKER_GHODIUM_CAPACITY),
+                    ready: false,
+                });
+            }
+
+            if object_type == "deposit" {
+                deposits.insert(
+                    object_id.clone(),
+                    DepositSummary {
+                        id: object_id.clone(),
+                        deposit_type: map_first_string(&record, &["depositType"]),
+                        x,
+                        y,
+                        last_cooldown: map_first_f64(&record, &["lastCooldown"]),
+                        cooldown: map_first_f64(&record, &["cooldown"]),
+                        cooldown_time: map_first_f64(&record, &["cooldownTime"]),
+                        decay_time: map_first_f64(&record, &["decayTime"]),
+                        ticks_to_decay: map_first_f64(&record, &["ticksToDecay"]),
+                    },
+                );
+            }
+
+            if object_type == "powerBank" {
+                power_banks.insert(
+                    object_id.clone(),
+                    PowerBankSummary {
+                        id: object_id.clone(),
+                        x,
+                        y,
+                        power: map_first_f64(&record, &["power"]).or_else(|| {
+                            record
+                                .get("store")
+                                .and_then(|store| store.get("power"))
+                                .and_then(value_as_f64)
+                        }),
+                        hits: record.get("hits").and_then(value_as_f64),
+                        hits_max: record.get("hitsMax").and_then(value_as_f64),
+                        decay_time: map_first_f64(&record, &["decayTime"]),
+                        ticks_remaining: map_first_f64(&record, &["ticksToDecay"]),
+                    },
+                );
+            }
+
+            // Labs also carry mineralType, so only non-structures fall back to being minerals.
+            if object_type == "mineral"
+                || (!is_structure_type(&object_type)
+                    && map_first_string(&record, &["mineralType"]).is_some())
+            {
                 minerals.insert(
                     format!("{}:{}", x, y),
                     RoomMineralSummary {
@@ -697,6 +1494,11 @@ fn parse_entities(
                             .or(Some(object_type.clone())),
                         x,
                         y,
+                        mineral_amount: map_first_f64(&record, &["mineralAmount"]),
+                        density: map_first_f64(&record, &["density"]),
+                        regeneration_time: map_first_f64(&record, &["nextRegenerationTime"]),
+                        ticks_to_regeneration: map_first_f64(&record, &["ticksToRegeneration"]),
+                        depleted: false,
                     },
                 );
                 continue;
@@ -709,36 +1511,80 @@ fn parse_entities(
                 if controller_level.is_none() {
                     controller_level = map_first_f64(&record, &["level"]);
                 }
+                if controller_downgrade_time.is_none() {
+                    controller_downgrade_time = map_first_f64(&record, &["downgradeTime"]);
+                }
+                if controller_ticks_to_downgrade.is_none() {
+                    controller_ticks_to_downgrade = map_first_f64(&record, &["ticksToDowngrade"]);
+                }
+                if safe_mode_until.is_none() {
+                    safe_mode_until = map_first_f64(&record, &["safeMode"]);
+                }
+                if safe_mode_available.is_none() {
+                    safe_mode_available = map_first_f64(&record, &["safeModeAvailable"]);
+                }
+                if controller_reservation.is_none() {
+                    controller_reservation = parse_reservation(record.get("reservation"));
+                }
+                if controller_sign.is_none() {
+                    controller_sign = parse_controller_sign(record.get("sign"));
+                }
                 continue;
             }
 
-            if object_type == "creep" || object_type == "powerCreep" {
+            if object_type == "powerCreep" {
                 let creep_name =
                     object_name.unwrap_or_else(|| format!("{}-{}-{}", object_type, x, y));
-                creeps.insert(
+                power_creeps.insert(
                     creep_name.clone(),
-                    RoomCreepSummary {
+                    PowerCreepSummary {
                         name: creep_name,
-                        role: map_first_string(&record, &["role"]),
                         x,
                         y,
+                        class_name: map_first_string(&record, &["className", "class"]),
+                        level: record.get("level").and_then(value_as_f64),
+                        powers: parse_powers(record.get("powers")),
                         ttl: map_first_f64(&record, &["ticksToLive", "ttl"]),
                     },
                 );
                 continue;
             }
 
-            if is_structure_type(&object_type) {
-                structures.insert(
-                    format!("{}:{}:{}", object_type, x, y),
-                    RoomStructureSummary {
-                        r#type: object_type.clone(),
+            if object_type == "creep" {
+                let creep_name =
+                    object_name.unwrap_or_else(|| format!("{}-{}-{}", object_type, x, y));
+                creeps.insert(
+                    creep_name.clone(),
+                    RoomCreepSummary {
+                        name: creep_name,
+                        role: map_first_string(&record, &["role"]),
                         x,
                         y,
-                        hits: record.get("hits").and_then(value_as_f64),
-                        hits_max: record.get("hitsMax").and_then(value_as_f64),
+                        ttl: map_first_f64(&record, &["ticksToLive", "ttl"]),
+                        body_stats,
+                        owner: map_first_string(&record, &["user", "owner"]),
+                        disposition: None,
                     },
                 );
+                continue;
+            }
+
+            if is_structure_type(&object_type) {
+                let structure_key = format!("{}:{}:{}", object_type, x, y);
+                let structure = RoomStructureSummary {
+                    r#type: object_type.clone(),
+                    x,
+                    y,
+                    hits: record.get("hits").and_then(value_as_f64),
+                    hits_max: record.get("hitsMax").and_then(value_as_f64),
+                    resources: structure_resources,
+                };
+                if let (Some(hits), Some(hits_max)) = (structure.hits, structure.hits_max) {
+                    if hits < hits_max * damage_threshold {
+                        damaged_structures.insert(structure_key.clone(), structure.clone());
+                    }
+                }
+                structures.insert(structure_key, structure);
                 if object_type == "spawn" || object_type == "extension" {
                     if let Some(value) = object_energy {
                         energy_available = Some(energy_available.unwrap_or(0.0) + value);
@@ -751,18 +1597,184 @@ fn parse_entities(
         }
     }
 
+    let mut resource_totals = HashMap::<String, f64>::new();
+    for store in objects.values().filter_map(|item| item.store.as_ref()) {
+        for (resource, amount) in store {
+            *resource_totals.entry(resource.clone()).or_insert(0.0) += amount;
+        }
+    }
+
     ParsedEntities {
         shard,
         owner,
         controller_level,
+        controller_downgrade_time,
+        controller_ticks_to_downgrade,
+        safe_mode_until,
+        safe_mode_available,
+        controller_reservation,
+        controller_sign,
         energy_available,
         energy_capacity,
+        resource_totals,
         sources: sources.into_values().collect(),
         minerals: minerals.into_values().collect(),
+        deposits: deposits.into_values().collect(),
+        power_banks: power_banks.into_values().collect(),
         structures: structures.into_values().collect(),
+        damaged_structures: damaged_structures.into_values().collect(),
         creeps: creeps.into_values().collect(),
+        power_creeps: power_creeps.into_values().collect(),
+        construction_sites: construction_sites.into_values().collect(),
         objects: objects.into_values().collect(),
+        spawns: spawns.into_values().collect(),
+        labs: labs.into_values().collect(),
+        nuker,
+    }
+}
+
+fn classify_creeps(
+    creeps: &mut [RoomCreepSummary],
+    self_user_id: Option<&str>,
+) -> (Vec<RoomCreepSummary>, Option<f64>) {
+    let Some(self_user_id) = self_user_id.map(str::trim).filter(|value| !value.is_empty()) else {
+        return (Vec::new(), None);
+    };
+    let mut hostiles = Vec::new();
+    let mut threat_score = 0.0;
+    for creep in creeps.iter_mut() {
+        let disposition = match creep.owner.as_deref() {
+            Some(owner) if owner == self_user_id => "friendly",
+            Some(owner) if owner != SOURCE_KEEPER_USER_ID => "hostile",
+            _ => "neutral",
+        };
+        creep.disposition = Some(disposition.to_string());
+        if disposition != "hostile" {
+            continue;
+        }
+        if let Some(stats) = creep.body_stats.as_ref() {
+            threat_score += ["attack", "ranged_attack", "heal"]
+                .iter()
+                .filter_map(|part| stats.parts.get(*part))
+                .sum::<u32>() as f64;
+        }
+        hostiles.push(creep.clone());
+    }
+    (hostiles, Some(threat_score))
+}
+
+fn resolve_downgrade(
+    downgrade_time: Option<f64>,
+    ticks_to_downgrade: Option<f64>,
+    game_time: Option<f64>,
+) -> (Option<f64>, Option<f64>) {
+    let absolute = downgrade_time.or_else(|| Some(game_time? + ticks_to_downgrade?));
+    let relative = ticks_to_downgrade.or_else(|| Some((downgrade_time? - game_time?).max(0.0)));
+    (absolute, relative)
+}
+
+fn resolve_lab_cooldowns(mut labs: Vec<LabSummary>, game_time: Option<f64>) -> Vec<LabSummary> {
+    for lab in labs.iter_mut() {
+        let (_, cooldown) = resolve_downgrade(lab.cooldown_time, lab.cooldown, game_time);
+        lab.cooldown = cooldown;
+        lab.reaction_ready = cooldown.unwrap_or(0.0) <= 0.0;
+    }
+    labs.sort_by(|left, right| left.id.cmp(&right.id));
+    labs
+}
+
+fn resolve_minerals(
+    mut minerals: Vec<RoomMineralSummary>,
+    game_time: Option<f64>,
+) -> Vec<RoomMineralSummary> {
+    for mineral in minerals.iter_mut() {
+        mineral.depleted = mineral.mineral_amount.is_some_and(|amount| amount <= 0.0);
+        // The regeneration tick is only meaningful while the mineral is empty.
+        if !mineral.depleted {
+            mineral.regeneration_time = None;
+            mineral.ticks_to_regeneration = None;
+            continue;
+        }
+        let (regeneration_time, ticks_to_regeneration) =
+            resolve_downgrade(mineral.regeneration_time, mineral.ticks_to_regeneration, game_time);
+        mineral.regeneration_time = regeneration_time;
+        mineral.ticks_to_regeneration = ticks_to_regeneration;
+    }
+    minerals
+}
+
+fn resolve_deposits(
+    mut deposits: Vec<DepositSummary>,
+    game_time: Option<f64>,
+) -> Vec<DepositSummary> {
+    for deposit in deposits.iter_mut() {
+        let (cooldown_time, cooldown) =
+            resolve_downgrade(deposit.cooldown_time, deposit.cooldown, game_time);
+        deposit.cooldown_time = cooldown_time;
+        deposit.cooldown = cooldown;
+        let (decay_time, ticks_to_decay) =
+            resolve_downgrade(deposit.decay_time, deposit.ticks_to_decay, game_time);
+        deposit.decay_time = decay_time;
+        deposit.ticks_to_decay = ticks_to_decay;
+    }
+    deposits.sort_by(|left, right| left.id.cmp(&right.id));
+    deposits
+}
+
+fn resolve_power_banks(
+    mut power_banks: Vec<PowerBankSummary>,
+    game_time: Option<f64>,
+) -> Vec<PowerBankSummary> {
+    for bank in power_banks.iter_mut() {
+        let (decay_time, ticks_remaining) =
+            resolve_downgrade(bank.decay_time, bank.ticks_remaining, game_time);
+        bank.decay_time = decay_time;
+        bank.ticks_remaining = ticks_remaining;
+    }
+    power_banks.sort_by(|left, right| left.id.cmp(&right.id));
+    power_banks
+}
+
+fn resolve_power_creeps(
+    mut power_creeps: Vec<PowerCreepSummary>,
+    game_time: Option<f64>,
+) -> Vec<PowerCreepSummary> {
+    for power in power_creeps.iter_mut().flat_map(|creep| creep.powers.iter_mut()) {
+        let (_, cooldown) = resolve_downgrade(power.cooldown_time, power.cooldown, game_time);
+        power.cooldown = cooldown;
+    }
+    power_creeps.sort_by(|left, right| left.name.cmp(&right.name));
+    power_creeps
+}
+
+fn resolve_spawns(mut spawns: Vec<SpawnSummary>, game_time: Option<f64>) -> Vec<SpawnSummary> {
+    for progress in spawns.iter_mut().filter_map(|spawn| spawn.spawning.as_mut()) {
+        let (_, remaining_time) =
+            resolve_downgrade(progress.spawn_time, progress.remaining_time, game_time);
+        progress.remaining_time = remaining_time;
+        progress.progress_pct = match (progress.need_time, remaining_time) {
+            (Some(need_time), Some(remaining_time)) if need_time > 0.0 => {
+                Some(((need_time - remaining_time) / need_time * 100.0).clamp(0.0, 100.0))
+            }
+            _ => None,
+        };
     }
+    spawns.sort_by(|left, right| left.name.cmp(&right.name).then(left.x.cmp(&right.x)));
+    spawns
+}
+
+fn resolve_nuker(nuker: Option<NukerSummary>, game_time: Option<f64>) -> Option<NukerSummary> {
+    let mut nuker = nuker?;
+    let (_, cooldown) = resolve_downgrade(nuker.cooldown_time, nuker.cooldown, game_time);
+    nuker.cooldown = cooldown;
+    nuker.ready = nuker.energy >= nuker.energy_capacity
+        && nuker.ghodium >= nuker.ghodium_capacity
+        && cooldown.unwrap_or(0.0) <= 0.0;
+    Some(nuker)
+}
+
+fn damage_threshold_for(threshold: Option<f64>) -> f64 {
+    threshold.filter(|value| value.is_finite()).unwrap_or(DEFAULT_DAMAGE_THRESHOLD).clamp(0.0, 1.0)
 }
 
 fn merge_by_key<T>(primary: Vec<T>, secondary: Vec<T>, key_of: impl Fn(&T) -> String) -> Vec<T> {
@@ -869,6 +1881,111 @@ fn extract_terrain(payload: &Value) -> Option<String> {
         .or_else(|| root.get("encodedTerrain").and_then(value_as_non_empty_string))
 }
 
+fn decode_terrain_grid(encoded: &str) -> Result<Vec<Vec<u8>>, String> {
+    let digits = encoded.as_bytes();
+    if digits.len() != 2500 {
+        return Err(format!("Encoded terrain must be 2500 characters, got {}", digits.len()));
+    }
+    let mut grid = Vec::with_capacity(50);
+    for row in digits.chunks(50) {
+        let mut cells = Vec::with_capacity(50);
+        for digit in row {
+            match digit {
+                b'0'..=b'3' => cells.push(digit - b'0'),
+                _ => return Err(format!("Invalid terrain value: {}", *digit as char)),
+            }
+        }
+        grid.push(cells);
+    }
+    Ok(grid)
+}
+
+fn build_render_frame(
+    terrain_encoded: Option<&str>,
+    objects: &[RoomObjectSummary],
+) -> Option<RoomRenderFrame> {
+    let grid = decode_terrain_grid(terrain_encoded?).ok()?;
+    let mut tiles = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter().enumerate().map(move |(x, code)| Tile {
+                x: x as i64,
+                y: y as i64,
+                terrain: match code {
+                    1 | 3 => "wall",
+                    2 => "swamp",
+                    _ => "plain",
+                }
+                .to_string(),
+                object_ids: Vec::new(),
+            })
+        })
+        .collect::<Vec<Tile>>();
+    for object in objects {
+        if (0..50).contains(&object.x) && (0..50).contains(&object.y) {
+            tiles[(object.y * 50 + object.x) as usize].object_ids.push(object.id.clone());
+        }
+    }
+    Some(RoomRenderFrame { tiles })
+}
+
+fn extract_room_history(payload: &Value, interval: u32) -> Option<RoomHistorySeries> {
+    let root = as_object(payload)?;
+    let stats = root.get("stats").and_then(as_object)?;
+    let mut series = HashMap::<String, Vec<RoomHistoryPoint>>::new();
+    for (label, raw_points) in stats {
+        let Some(items) = raw_points.as_array() else {
+            continue;
+        };
+        let points = items
+            .iter()
+            .filter_map(|item| {
+                let record = as_object(item)?;
+                Some(RoomHistoryPoint {
+                    end_time: record.get("endTime").and_then(value_as_f64),
+                    value: record.get("value").and_then(value_as_f64)?,
+                })
+            })
+            .collect::<Vec<RoomHistoryPoint>>();
+        series.insert(label.clone(), points);
+    }
+    let maxima = collect_numeric_map(root.get("statsMax")).unwrap_or_default();
+    if series.is_empty() && maxima.is_empty() {
+        return None;
+    }
+    Some(RoomHistorySeries { interval, series, maxima })
+}
+
+// Some(..) means map-stats knows the room exists, even when it has no owner.
+fn extract_map_stats_room(
+    payload: &Value,
+    room_name: &str,
+) -> Option<(Option<String>, Option<f64>)> {
+    let root = as_object(payload)?;
+    let stat = root.get("stats").and_then(as_object)?.get(room_name).and_then(as_object)?;
+    let own = stat.get("own").and_then(as_object);
+    let owner = own.and_then(|own| map_first_string(own, &["user"])).map(|id| {
+        root.get("users")
+            .and_then(as_object)
+            .and_then(|users| users.get(&id))
+            .and_then(as_object)
+            .and_then(|user| map_first_string(user, &["username"]))
+            .unwrap_or(id)
+    });
+    Some((owner, own.and_then(|own| map_first_f64(own, &["level"]))))
+}
+
+fn resolve_visibility(has_objects: bool, has_terrain: bool, known_to_map: bool) -> RoomVisibility {
+    if has_objects {
+        RoomVisibility::Visible
+    } else if has_terrain || known_to_map {
+        RoomVisibility::Invisible
+    } else {
+        RoomVisibility::Unknown
+    }
+}
+
 fn extract_game_time(payload: &Value) -> Option<f64> {
     let root = as_object(payload)?;
     map_first_f64(root, &["gameTime", "time", "tick"])
@@ -891,13 +2008,39 @@ fn build_request(
         username: Some(username.to_string()),
         query,
         body,
+        ..Default::default()
     }
 }
 
+fn strip_shard_variants(requests: Vec<ScreepsRequest>) -> Vec<ScreepsRequest> {
+    requests
+        .into_iter()
+        .filter(|request| request.query.as_ref().is_none_or(|query| !query.contains_key("shard")))
+        .map(|mut request| {
+            if let Some(Value::Object(body)) = request.body.as_mut() {
+                body.remove("shard");
+            }
+            request
+        })
+        .collect()
+}
+
 async fn request_first_success(requests: Vec<ScreepsRequest>) -> Option<Value> {
     let client = shared_http_client().ok()?;
+    let shardless = match requests.first() {
+        Some(request) => {
+            is_single_shard_server(
+                &request.base_url,
+                request.token.as_deref(),
+                request.username.as_deref(),
+            )
+            .await
+        }
+        None => false,
+    };
+    let requests = if shardless { strip_shard_variants(requests) } else { requests };
     for request in requests {
-        let Ok(response) = perform_screeps_request(client, request).await else {
+        let Ok(response) = perform_screeps_request(&client, request).await else {
             continue;
         };
         if response.ok {
@@ -907,6 +2050,52 @@ async fn request_first_success(requests: Vec<ScreepsRequest>) -> Option<Value> {
     None
 }
 
+async fn fetch_room_objects_payload(
+    base_url: &str,
+    token: &str,
+    username: &str,
+    room_name: &str,
+    shard: Option<&str>,
+) -> Option<Value> {
+    let shard_value = shard.unwrap_or("shard0");
+    request_first_success(vec![
+        build_request(
+            base_url,
+            token,
+            username,
+            "/api/game/room-objects",
+            "GET",
+            Some(HashMap::from([
+                ("room".to_string(), json!(room_name)),
+                ("shard".to_string(), json!(shard_value)),
+            ])),
+            None,
+        ),
+        build_request(
+            base_url,
+            token,
+            username,
+            "/api/game/room-objects",
+            "POST",
+            None,
+            Some(json!({
+                "room": room_name,
+                "shard": shard,
+            })),
+        ),
+        build_request(
+            base_url,
+            token,
+            username,
+            "/api/game/room-objects",
+            "GET",
+            Some(HashMap::from([("room".to_string(), json!(room_name))])),
+            None,
+        ),
+    ])
+    .await
+}
+
 fn fetched_at_millis() -> String {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -914,9 +2103,8 @@ fn fetched_at_millis() -> String {
         .unwrap_or_else(|_| "0".to_string())
 }
 
-#[tauri::command]
-pub async fn screeps_room_detail_fetch(
-    request: ScreepsRoomDetailRequest,
+pub(crate) async fn fetch_room_detail(
+    request: &ScreepsRoomDetailRequest,
 ) -> Result<RoomDetailSnapshot, String> {
     if request.token.trim().is_empty() {
         return Err("Token cannot be empty".to_string());
@@ -926,9 +2114,33 @@ pub async fn screeps_room_detail_fetch(
     }
 
     let room_name = normalize_room_name(&request.room_name)?;
-    let shard = normalize_shard(request.shard.as_deref());
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
     let shard_value = shard.clone().unwrap_or_else(|| "shard0".to_string());
 
+    // A failed time check just falls through to the full fetch.
+    if let Some(since_game_time) = request.since_game_time {
+        let current = fetch_game_time(ScreepsGameTimeRequest {
+            base_url: request.base_url.clone(),
+            token: Some(request.token.clone()),
+            username: Some(request.username.clone()),
+            shard: shard.clone(),
+        })
+        .await
+        .ok()
+        .map(|time| time as f64);
+        if let Some(game_time) = current.filter(|time| *time <= since_game_time) {
+            return Ok(RoomDetailSnapshot {
+                fetched_at: fetched_at_millis(),
+                not_modified: true,
+                room_coords: room_coords(&room_name),
+                room_name,
+                shard,
+                game_time: Some(game_time),
+                ..Default::default()
+            });
+        }
+    }
+
     let terrain_payload = request_first_success(vec![
         build_request(
             &request.base_url,
@@ -982,7 +2194,7 @@ pub async fn screeps_room_detail_fetch(
             "GET",
             Some(HashMap::from([
                 ("room".to_string(), Value::String(room_name.clone())),
-                ("interval".to_string(), json!(8)),
+                ("interval".to_string(), json!(ROOM_OVERVIEW_INTERVAL)),
                 ("shard".to_string(), Value::String(shard_value.clone())),
             ])),
             None,
@@ -996,48 +2208,20 @@ pub async fn screeps_room_detail_fetch(
             None,
             Some(json!({
                 "room": room_name.clone(),
-                "interval": 8,
+                "interval": ROOM_OVERVIEW_INTERVAL,
                 "shard": shard.clone(),
             })),
         ),
     ])
     .await;
 
-    let room_objects_payload = request_first_success(vec![
-        build_request(
-            &request.base_url,
-            &request.token,
-            &request.username,
-            "/api/game/room-objects",
-            "GET",
-            Some(HashMap::from([
-                ("room".to_string(), Value::String(room_name.clone())),
-                ("shard".to_string(), Value::String(shard_value.clone())),
-            ])),
-            None,
-        ),
-        build_request(
-            &request.base_url,
-            &request.token,
-            &request.username,
-            "/api/game/room-objects",
-            "POST",
-            None,
-            Some(json!({
-                "room": room_name.clone(),
-                "shard": shard.clone(),
-            })),
-        ),
-        build_request(
-            &request.base_url,
-            &request.token,
-            &request.username,
-            "/api/game/room-objects",
-            "GET",
-            Some(HashMap::from([("room".to_string(), Value::String(room_name.clone()))])),
-            None,
-        ),
-    ])
+    let room_objects_payload = fetch_room_objects_payload(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        &room_name,
+        shard.as_deref(),
+    )
     .await;
 
     let rooms_payload = if let Some(config) = request.rooms_endpoint.as_ref() {
@@ -1055,17 +2239,48 @@ pub async fn screeps_room_detail_fetch(
         None
     };
 
-    let parsed_room_objects =
-        parse_entities(&room_name, shard.clone(), &[room_objects_payload.as_ref()]);
+    let mut data_sources = HashMap::from([
+        ("terrain".to_string(), terrain_payload.is_some()),
+        ("mapStats".to_string(), map_stats_payload.is_some()),
+        ("overview".to_string(), overview_payload.is_some()),
+        ("roomObjects".to_string(), room_objects_payload.is_some()),
+    ]);
+    if request.rooms_endpoint.is_some() {
+        data_sources.insert("rooms".to_string(), rooms_payload.is_some());
+    }
+
+    let damage_threshold = damage_threshold_for(request.damage_threshold);
+    let parsed_room_objects = parse_entities(
+        &room_name,
+        shard.clone(),
+        &[room_objects_payload.as_ref()],
+        damage_threshold,
+    );
     let fallback_entities = parse_entities(
         &room_name,
         shard.clone(),
         &[map_stats_payload.as_ref(), rooms_payload.as_ref(), overview_payload.as_ref()],
+        damage_threshold,
     );
 
+    let map_stats_room =
+        map_stats_payload.as_ref().and_then(|payload| extract_map_stats_room(payload, &room_name));
+    let visibility = resolve_visibility(
+        !parsed_room_objects.objects.is_empty(),
+        terrain_payload.is_some(),
+        map_stats_room.is_some(),
+    );
+    let (map_stats_owner, map_stats_level) = map_stats_room.unwrap_or_default();
+
     let fallback_shard = fallback_entities.shard.clone();
-    let fallback_owner = fallback_entities.owner.clone();
-    let fallback_controller_level = fallback_entities.controller_level;
+    let fallback_owner = fallback_entities.owner.clone().or(map_stats_owner);
+    let fallback_controller_level = fallback_entities.controller_level.or(map_stats_level);
+    let fallback_downgrade_time = fallback_entities.controller_downgrade_time;
+    let fallback_ticks_to_downgrade = fallback_entities.controller_ticks_to_downgrade;
+    let fallback_safe_mode_until = fallback_entities.safe_mode_until;
+    let fallback_safe_mode_available = fallback_entities.safe_mode_available;
+    let fallback_reservation = fallback_entities.controller_reservation.clone();
+    let fallback_sign = fallback_entities.controller_sign.clone();
     let fallback_energy_available = fallback_entities.energy_available;
     let fallback_energy_capacity = fallback_entities.energy_capacity;
     let fallback_objects = to_fallback_objects(&fallback_entities);
@@ -1080,13 +2295,41 @@ pub async fn screeps_room_detail_fetch(
         merge_by_key(parsed_room_objects.structures, fallback_entities.structures, |item| {
             format!("{}:{}:{}", item.r#type, item.x, item.y)
         });
+    let damaged_structures = merge_by_key(
+        parsed_room_objects.damaged_structures,
+        fallback_entities.damaged_structures,
+        |item| format!("{}:{}:{}", item.r#type, item.x, item.y),
+    );
     let creeps = merge_by_key(parsed_room_objects.creeps, fallback_entities.creeps, |item| {
         item.name.clone()
     });
+    let construction_sites = merge_by_key(
+        parsed_room_objects.construction_sites,
+        fallback_entities.construction_sites,
+        |item| format!("{}:{}", item.x, item.y),
+    );
     let objects =
         merge_by_key(parsed_room_objects.objects, fallback_objects, |item| item.id.clone());
 
     let terrain_encoded = terrain_payload.as_ref().and_then(extract_terrain);
+    let terrain_grid = match terrain_encoded.as_deref() {
+        Some(encoded) if request.decode_terrain.unwrap_or(false) => {
+            Some(decode_terrain_grid(encoded)?)
+        }
+        _ => None,
+    };
+    let render_frame = if request.render_frame.unwrap_or(false) {
+        build_render_frame(terrain_encoded.as_deref(), &objects)
+    } else {
+        None
+    };
+    let history = if request.include_history.unwrap_or(false) {
+        overview_payload
+            .as_ref()
+            .and_then(|payload| extract_room_history(payload, ROOM_OVERVIEW_INTERVAL))
+    } else {
+        None
+    };
     let game_time = room_objects_payload
         .as_ref()
         .and_then(extract_game_time)
@@ -1094,21 +2337,413 @@ pub async fn screeps_room_detail_fetch(
         .or_else(|| map_stats_payload.as_ref().and_then(extract_game_time))
         .or_else(|| terrain_payload.as_ref().and_then(extract_game_time))
         .or_else(|| rooms_payload.as_ref().and_then(extract_game_time));
+    let (controller_downgrade_time, controller_ticks_to_downgrade) = resolve_downgrade(
+        parsed_room_objects.controller_downgrade_time.or(fallback_downgrade_time),
+        parsed_room_objects.controller_ticks_to_downgrade.or(fallback_ticks_to_downgrade),
+        game_time,
+    );
+    let mut creeps = creeps;
+    let (hostiles, threat_score) = classify_creeps(&mut creeps, request.self_user_id.as_deref());
+    let spawns = resolve_spawns(parsed_room_objects.spawns, game_time);
+    let labs = resolve_lab_cooldowns(parsed_room_objects.labs, game_time);
+    let nuker = resolve_nuker(parsed_room_objects.nuker, game_time);
+    let minerals = resolve_minerals(minerals, game_time);
+    let deposits = resolve_deposits(parsed_room_objects.deposits, game_time);
+    let power_banks = resolve_power_banks(parsed_room_objects.power_banks, game_time);
+    let power_creeps = resolve_power_creeps(parsed_room_objects.power_creeps, game_time);
 
     Ok(RoomDetailSnapshot {
         fetched_at: fetched_at_millis(),
+        not_modified: false,
+        visibility,
+        room_coords: room_coords(&room_name),
         room_name,
         shard: parsed_room_objects.shard.or(fallback_shard).or(shard),
         owner: parsed_room_objects.owner.or(fallback_owner),
         controller_level: parsed_room_objects.controller_level.or(fallback_controller_level),
+        controller_downgrade_time,
+        controller_ticks_to_downgrade,
+        safe_mode_until: parsed_room_objects.safe_mode_until.or(fallback_safe_mode_until),
+        safe_mode_available: parsed_room_objects
+            .safe_mode_available
+            .or(fallback_safe_mode_available),
+        controller_reservation: parsed_room_objects.controller_reservation.or(fallback_reservation),
+        controller_sign: parsed_room_objects.controller_sign.or(fallback_sign),
         energy_available: parsed_room_objects.energy_available.or(fallback_energy_available),
         energy_capacity: parsed_room_objects.energy_capacity.or(fallback_energy_capacity),
         terrain_encoded,
+        terrain_grid,
+        render_frame,
         game_time,
+        resource_totals: parsed_room_objects.resource_totals,
+        history,
+        data_sources,
         sources,
         minerals,
+        deposits,
+        power_banks,
         structures,
+        damaged_structures,
         creeps,
+        power_creeps,
+        construction_sites,
         objects,
+        spawns,
+        labs,
+        nuker,
+        hostiles,
+        threat_score,
+        self_user_id: request.self_user_id.clone(),
+        damage_threshold: request.damage_threshold,
+    })
+}
+
+pub(crate) fn merge_room_objects(
+    base: &RoomDetailSnapshot,
+    objects: &Map<String, Value>,
+    game_time: Option<f64>,
+) -> RoomDetailSnapshot {
+    if objects.is_empty() {
+        return RoomDetailSnapshot {
+            fetched_at: fetched_at_millis(),
+            not_modified: false,
+            game_time: game_time.or(base.game_time),
+            ..base.clone()
+        };
+    }
+
+    let payload = json!({ "objects": objects });
+    let parsed = parse_entities(
+        &base.room_name,
+        base.shard.clone(),
+        &[Some(&payload)],
+        damage_threshold_for(base.damage_threshold),
+    );
+    let game_time = game_time.or(base.game_time);
+    let (controller_downgrade_time, controller_ticks_to_downgrade) =
+        match (parsed.controller_downgrade_time, parsed.controller_ticks_to_downgrade) {
+            (None, None) => resolve_downgrade(base.controller_downgrade_time, None, game_time),
+            (downgrade_time, ticks) => resolve_downgrade(downgrade_time, ticks, game_time),
+        };
+    let mut creeps = parsed.creeps;
+    let (hostiles, threat_score) = classify_creeps(&mut creeps, base.self_user_id.as_deref());
+    let render_frame = base
+        .render_frame
+        .as_ref()
+        .and_then(|_| build_render_frame(base.terrain_encoded.as_deref(), &parsed.objects));
+    RoomDetailSnapshot {
+        fetched_at: fetched_at_millis(),
+        not_modified: false,
+        room_name: base.room_name.clone(),
+        visibility: RoomVisibility::Visible,
+        room_coords: base.room_coords.clone(),
+        shard: parsed.shard.or_else(|| base.shard.clone()),
+        owner: parsed.owner.or_else(|| base.owner.clone()),
+        controller_level: parsed.controller_level.or(base.controller_level),
+        controller_downgrade_time,
+        controller_ticks_to_downgrade,
+        safe_mode_until: parsed.safe_mode_until.or(base.safe_mode_until),
+        safe_mode_available: parsed.safe_mode_available.or(base.safe_mode_available),
+        controller_reservation: parsed.controller_reservation,
+        controller_sign: parsed.controller_sign,
+        energy_available: parsed.energy_available.or(base.energy_available),
+        energy_capacity: parsed.energy_capacity.or(base.energy_capacity),
+        terrain_encoded: base.terrain_encoded.clone(),
+        terrain_grid: base.terrain_grid.clone(),
+        render_frame,
+        game_time,
+        resource_totals: parsed.resource_totals,
+        history: base.history.clone(),
+        data_sources: base.data_sources.clone(),
+        sources: parsed.sources,
+        minerals: resolve_minerals(parsed.minerals, game_time),
+        deposits: resolve_deposits(parsed.deposits, game_time),
+        power_banks: resolve_power_banks(parsed.power_banks, game_time),
+        structures: parsed.structures,
+        damaged_structures: parsed.damaged_structures,
+        creeps,
+        power_creeps: resolve_power_creeps(parsed.power_creeps, game_time),
+        construction_sites: parsed.construction_sites,
+        objects: parsed.objects,
+        spawns: resolve_spawns(parsed.spawns, game_time),
+        labs: resolve_lab_cooldowns(parsed.labs, game_time),
+        nuker: resolve_nuker(parsed.nuker, game_time),
+        hostiles,
+        threat_score,
+        self_user_id: base.self_user_id.clone(),
+        damage_threshold: base.damage_threshold,
+    }
+}
+
+#[tauri::command]
+pub async fn screeps_room_detail_fetch(
+    request: ScreepsRoomDetailRequest,
+) -> Result<RoomDetailSnapshot, String> {
+    fetch_room_detail(&request).await
+}
+
+#[tauri::command]
+pub async fn screeps_rooms_detail_fetch_many(
+    app: AppHandle,
+    batch: ScreepsRoomsDetailBatchRequest,
+) -> Result<HashMap<String, Result<RoomDetailSnapshot, String>>, String> {
+    let max_concurrency = batch.max_concurrency.unwrap_or(8).clamp(1, 16);
+    let mut output = HashMap::<String, Result<RoomDetailSnapshot, String>>::new();
+    let total = batch.room_names.len();
+    let completed = Arc::new(AtomicUsize::new(0));
+
+    for chunk in batch.room_names.chunks(max_concurrency) {
+        let mut handles = Vec::with_capacity(chunk.len());
+        for room_name in chunk {
+            let request = ScreepsRoomDetailRequest {
+                base_url: batch.base_url.clone(),
+                token: batch.token.clone(),
+                username: batch.username.clone(),
+                room_name: room_name.clone(),
+                shard: batch.shard.clone(),
+                rooms_endpoint: batch.rooms_endpoint.clone(),
+                decode_terrain: batch.decode_terrain,
+                include_history: batch.include_history,
+                render_frame: batch.render_frame,
+                self_user_id: batch.self_user_id.clone(),
+                damage_threshold: batch.damage_threshold,
+                since_game_time: None,
+            };
+            let task_app = app.clone();
+            let task_completed = completed.clone();
+            let correlation_id = batch.correlation_id.clone();
+            handles.push(tauri::async_runtime::spawn(async move {
+                let result = fetch_room_detail(&request).await;
+                let _ = task_app.emit(
+                    "screeps://rooms-progress",
+                    ScreepsRoomsProgressEvent {
+                        correlation_id,
+                        room_name: request.room_name.clone(),
+                        ok: result.is_ok(),
+                        completed: task_completed.fetch_add(1, Ordering::Relaxed) + 1,
+                        total,
+                    },
+                );
+                (request.room_name, result)
+            }));
+        }
+
+        for handle in handles {
+            let (room_name, result) =
+                handle.await.map_err(|error| format!("room detail task failed: {}", error))?;
+            output.insert(room_name, result);
+        }
+    }
+
+    let _ = app.emit(
+        "screeps://rooms-done",
+        ScreepsRoomsDoneEvent {
+            correlation_id: batch.correlation_id.clone(),
+            completed: completed.load(Ordering::Relaxed),
+            total,
+        },
+    );
+    Ok(output)
+}
+
+async fn fetch_room_stockpile(
+    request: &ScreepsEmpireResourcesRequest,
+    room_name: &str,
+) -> Result<HashMap<String, f64>, String> {
+    let room_name = normalize_room_name(room_name)?;
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let payload = fetch_room_objects_payload(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        &room_name,
+        shard.as_deref(),
+    )
+    .await
+    .ok_or_else(|| format!("{}: room objects request failed", room_name))?;
+
+    let parsed = parse_entities(&room_name, shard, &[Some(&payload)], DEFAULT_DAMAGE_THRESHOLD);
+    let mut stockpile = HashMap::<String, f64>::new();
+    for store in parsed
+        .objects
+        .iter()
+        .filter(|item| matches!(item.r#type.as_str(), "terminal" | "storage" | "factory"))
+        .filter_map(|item| item.store.as_ref())
+    {
+        for (resource, amount) in store {
+            *stockpile.entry(resource.clone()).or_insert(0.0) += amount;
+        }
+    }
+    Ok(stockpile)
+}
+
+#[tauri::command]
+pub async fn screeps_empire_resources(
+    request: ScreepsEmpireResourcesRequest,
+) -> Result<EmpireResourcesSummary, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    if request.username.trim().is_empty() {
+        return Err("Username cannot be empty".to_string());
+    }
+
+    let max_concurrency = request.max_concurrency.unwrap_or(8).clamp(1, 16);
+    let mut summary = EmpireResourcesSummary::default();
+
+    for chunk in request.room_names.chunks(max_concurrency) {
+        let mut handles = Vec::with_capacity(chunk.len());
+        for room_name in chunk {
+            let request = request.clone();
+            let room_name = room_name.clone();
+            handles.push(tauri::async_runtime::spawn(async move {
+                let result = fetch_room_stockpile(&request, &room_name).await;
+                (room_name, result)
+            }));
+        }
+
+        for handle in handles {
+            let (room_name, result) =
+                handle.await.map_err(|error| format!("room resources task failed: {}", error))?;
+            match result {
+                Ok(stockpile) => {
+                    for (resource, amount) in &stockpile {
+                        *summary.totals.entry(resource.clone()).or_insert(0.0) += amount;
+                    }
+                    summary.rooms.insert(room_name.trim().to_ascii_uppercase(), stockpile);
+                }
+                Err(error) => summary.warnings.push(error),
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn assess_threat_level(
+    threat: &ThreatBreakdown,
+    hostile_count: usize,
+    tower_energy: f64,
+    safe_mode_active: bool,
+    incoming_nukes: usize,
+) -> ThreatLevel {
+    // Safe mode stops hostile creeps but not nukes.
+    if incoming_nukes > 0 {
+        return ThreatLevel::Critical;
+    }
+    let level = if threat.parts >= CRITICAL_THREAT_PARTS {
+        ThreatLevel::Critical
+    } else if threat.parts >= HIGH_THREAT_PARTS || (threat.parts > 0.0 && tower_energy <= 0.0) {
+        ThreatLevel::High
+    } else if hostile_count > 0 {
+        ThreatLevel::Low
+    } else {
+        ThreatLevel::None
+    };
+    if safe_mode_active {
+        level.min(ThreatLevel::Low)
+    } else {
+        level
+    }
+}
+
+#[tauri::command]
+pub async fn screeps_room_defense(
+    request: ScreepsRoomDefenseRequest,
+) -> Result<RoomDefenseReport, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    if request.username.trim().is_empty() {
+        return Err("Username cannot be empty".to_string());
+    }
+    let room_name = normalize_room_name(&request.room_name)?;
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+
+    let payload = fetch_room_objects_payload(
+        &request.base_url,
+        &request.token,
+        &request.username,
+        &room_name,
+        shard.as_deref(),
+    )
+    .await
+    .ok_or_else(|| format!("{}: room objects request failed", room_name))?;
+    // The nuke feed is experimental and missing on most private servers; treat failures as none.
+    let incoming_nukes = fetch_nukes(ScreepsNukesRequest {
+        base_url: request.base_url.clone(),
+        token: request.token.clone(),
+        username: request.username.clone(),
+        shard: shard.clone(),
+    })
+    .await
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|nuke| nuke.room.eq_ignore_ascii_case(&room_name))
+    .collect::<Vec<NukeEntry>>();
+
+    let game_time = extract_game_time(&payload);
+    let mut parsed =
+        parse_entities(&room_name, shard.clone(), &[Some(&payload)], DEFAULT_DAMAGE_THRESHOLD);
+    // Without an explicit id, anything not owned by the room owner counts as hostile.
+    let self_user_id = request.self_user_id.clone().or_else(|| parsed.owner.clone());
+    let (hostiles, threat_parts) = classify_creeps(&mut parsed.creeps, self_user_id.as_deref());
+
+    let mut threat = ThreatBreakdown { parts: threat_parts.unwrap_or(0.0), ..Default::default() };
+    for stats in hostiles.iter().filter_map(|creep| creep.body_stats.as_ref()) {
+        threat.attack += stats.attack;
+        threat.ranged_attack += stats.ranged_attack;
+        threat.heal += stats.heal;
+    }
+
+    let mut towers = Vec::new();
+    let mut barriers = Vec::new();
+    for structure in parsed.structures {
+        match structure.r#type.as_str() {
+            "tower" => towers.push(DefenseTowerSummary {
+                x: structure.x,
+                y: structure.y,
+                energy: structure.resources.as_ref().and_then(|item| item.energy),
+                energy_capacity: structure.resources.as_ref().and_then(|item| item.energy_capacity),
+            }),
+            "rampart" | "constructedWall" | "wall" => barriers.push(structure),
+            _ => {}
+        }
+    }
+    towers.sort_by_key(|tower| (tower.y, tower.x));
+    barriers.sort_by(|left, right| {
+        left.hits
+            .unwrap_or(0.0)
+            .partial_cmp(&right.hits.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let safe_mode_active = match (parsed.safe_mode_until, game_time) {
+        (Some(until), Some(now)) => until > now,
+        (Some(until), None) => until > 0.0,
+        _ => false,
+    };
+    let tower_energy = towers.iter().filter_map(|tower| tower.energy).sum::<f64>();
+    let threat_level = assess_threat_level(
+        &threat,
+        hostiles.len(),
+        tower_energy,
+        safe_mode_active,
+        incoming_nukes.len(),
+    );
+
+    Ok(RoomDefenseReport {
+        room_name,
+        shard: parsed.shard.or(shard),
+        game_time,
+        towers,
+        barriers,
+        hostiles,
+        threat,
+        safe_mode_active,
+        safe_mode_until: parsed.safe_mode_until,
+        safe_mode_available: parsed.safe_mode_available,
+        incoming_nukes,
+        threat_level,
     })
 }
@@ -0,0 +1,138 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::http::{
+    normalize_base_url, perform_screeps_request, shared_http_client, ScreepsRequest,
+};
+
+static SHARD_NAMES: OnceLock<Mutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+static SHARD_PROBE_FAILURES: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+// Long enough that a burst of shard-aware calls probes a failing server once, short enough to
+// pick up a server that recovers or a token that gets fixed.
+const SHARD_PROBE_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsShardsListRequest {
+    pub base_url: String,
+    pub token: Option<String>,
+    pub username: Option<String>,
+}
+
+fn shard_names() -> &'static Mutex<HashMap<String, Vec<String>>> {
+    SHARD_NAMES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn shard_probe_failures() -> &'static Mutex<HashMap<String, Instant>> {
+    SHARD_PROBE_FAILURES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub(crate) fn known_shard_name(base_url: &str, shard: &str) -> Option<String> {
+    let guard = shard_names().lock().ok()?;
+    guard
+        .get(&normalize_base_url(base_url))?
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(shard))
+        .cloned()
+}
+
+// Uses the names recorded by an earlier shard list fetch, fetching them once if none are known.
+pub(crate) async fn shard_names_for(
+    base_url: &str,
+    token: Option<&str>,
+    username: Option<&str>,
+) -> Option<Vec<String>> {
+    let key = normalize_base_url(base_url);
+    let cached = shard_names().lock().ok()?.get(&key).cloned();
+    if cached.is_some() {
+        return cached;
+    }
+    let recently_failed = shard_probe_failures()
+        .lock()
+        .ok()?
+        .get(&key)
+        .is_some_and(|failed_at| failed_at.elapsed() < SHARD_PROBE_RETRY_AFTER);
+    if recently_failed {
+        return None;
+    }
+    let result = fetch_shard_names(ScreepsShardsListRequest {
+        base_url: base_url.to_string(),
+        token: token.map(str::to_string),
+        username: username.map(str::to_string),
+    })
+    .await;
+    if let Ok(mut guard) = shard_probe_failures().lock() {
+        match result {
+            Ok(_) => guard.remove(&key),
+            Err(_) => guard.insert(key, Instant::now()),
+        };
+    }
+    result.ok()
+}
+
+// Single-shard private servers reject a shard parameter outright, so callers drop it for them.
+pub(crate) async fn is_single_shard_server(
+    base_url: &str,
+    token: Option<&str>,
+    username: Option<&str>,
+) -> bool {
+    shard_names_for(base_url, token, username).await.is_some_and(|names| names.len() <= 1)
+}
+
+async fn fetch_shard_names(request: ScreepsShardsListRequest) -> Result<Vec<String>, String> {
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: request.base_url.clone(),
+            endpoint: "/api/game/shards/info".to_string(),
+            method: Some("GET".to_string()),
+            token: request.token,
+            username: request.username,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    // Servers without the endpoint only have the one unnamed shard; remember that too.
+    if response.status == 404 {
+        if let Ok(mut guard) = shard_names().lock() {
+            guard.insert(normalize_base_url(&request.base_url), Vec::new());
+        }
+        return Ok(Vec::new());
+    }
+    if !response.ok {
+        return Err(format!("shards info request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = response.data.get("error").and_then(Value::as_str) {
+        return Err(error.trim().to_string());
+    }
+
+    let names = response
+        .data
+        .get("shards")
+        .and_then(Value::as_array)
+        .map(|shards| {
+            shards
+                .iter()
+                .filter_map(|shard| shard.get("name").and_then(Value::as_str))
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+
+    if let Ok(mut guard) = shard_names().lock() {
+        guard.insert(normalize_base_url(&request.base_url), names.clone());
+    }
+    Ok(names)
+}
+
+#[tauri::command]
+pub async fn screeps_shards_list(request: ScreepsShardsListRequest) -> Result<Vec<String>, String> {
+    fetch_shard_names(request).await
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::http::{
+    normalize_base_url, perform_screeps_request, shared_http_client, ScreepsRequest,
+};
+
+static USER_BADGE_CACHE: OnceLock<Mutex<HashMap<String, UserBadge>>> = OnceLock::new();
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsUserBadgeRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsUserFindRequest {
+    pub base_url: String,
+    pub token: String,
+    pub username: Option<String>,
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsUserFindResponse {
+    pub id: String,
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge: Option<UserBadge>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gcl: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserBadge {
+    pub r#type: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color2: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color3: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param: Option<f64>,
+    #[serde(default)]
+    pub flip: bool,
+}
+
+fn value_as_non_empty_string(value: &Value) -> Option<String> {
+    let trimmed = value.as_str()?.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn parse_user_badge(user: &Value) -> Option<UserBadge> {
+    let badge = user.get("badge").filter(|badge| badge.is_object())?;
+    serde_json::from_value::<UserBadge>(badge.clone()).ok()
+}
+
+fn user_badge_cache() -> &'static Mutex<HashMap<String, UserBadge>> {
+    USER_BADGE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn badge_cache_key(base_url: &str, username: &str) -> String {
+    format!("{}|{}", normalize_base_url(base_url), username.to_ascii_lowercase())
+}
+
+pub(crate) async fn fetch_user(
+    base_url: &str,
+    token: &str,
+    query: (&str, &str),
+) -> Result<Value, String> {
+    if token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let (key, value) = query;
+    let client = shared_http_client()?;
+    let response = perform_screeps_request(
+        &client,
+        ScreepsRequest {
+            base_url: base_url.to_string(),
+            endpoint: "/api/user/find".to_string(),
+            method: Some("GET".to_string()),
+            token: Some(token.to_string()),
+            query: Some(HashMap::from([(key.to_string(), Value::String(value.to_string()))])),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    // Unknown users come back as `ok: 0` (sometimes with a 404), not as a payload error.
+    if response.status == 404 || response.data.get("ok").and_then(Value::as_i64) == Some(0) {
+        return Err("user not found".to_string());
+    }
+    if !response.ok {
+        return Err(format!("user lookup request failed: HTTP {}", response.status));
+    }
+    if let Some(error) = response.data.get("error").and_then(value_as_non_empty_string) {
+        return Err(error);
+    }
+    response
+        .data
+        .get("user")
+        .filter(|user| user.is_object())
+        .cloned()
+        .ok_or_else(|| "user not found".to_string())
+}
+
+#[tauri::command]
+pub async fn screeps_user_badge(
+    request: ScreepsUserBadgeRequest,
+) -> Result<Option<UserBadge>, String> {
+    let username = request.username.trim();
+    if username.is_empty() {
+        return Err("Username cannot be empty".to_string());
+    }
+    let cache_key = badge_cache_key(&request.base_url, username);
+    if let Some(badge) =
+        user_badge_cache().lock().ok().and_then(|guard| guard.get(&cache_key).cloned())
+    {
+        return Ok(Some(badge));
+    }
+
+    let user = fetch_user(&request.base_url, &request.token, ("username", username)).await?;
+    let Some(badge) = parse_user_badge(&user) else {
+        return Ok(None);
+    };
+
+    if let Ok(mut guard) = user_badge_cache().lock() {
+        guard.insert(cache_key, badge.clone());
+    }
+    Ok(Some(badge))
+}
+
+#[tauri::command]
+pub async fn screeps_user_find(
+    request: ScreepsUserFindRequest,
+) -> Result<ScreepsUserFindResponse, String> {
+    let username = request.username.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    let id = request.id.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    let query = match (username, id) {
+        (Some(username), _) => ("username", username),
+        (None, Some(id)) => ("id", id),
+        (None, None) => return Err("Either username or id is required".to_string()),
+    };
+
+    let user = fetch_user(&request.base_url, &request.token, query).await?;
+    let id = user.get("_id").and_then(value_as_non_empty_string);
+    let username = user.get("username").and_then(value_as_non_empty_string);
+    let (Some(id), Some(username)) = (id, username) else {
+        return Err("user not found".to_string());
+    };
+
+    let badge = parse_user_badge(&user);
+    if let (Some(badge), Ok(mut guard)) = (badge.as_ref(), user_badge_cache().lock()) {
+        guard.insert(badge_cache_key(&request.base_url, &username), badge.clone());
+    }
+
+    Ok(ScreepsUserFindResponse {
+        id,
+        username,
+        badge,
+        gcl: user.get("gcl").and_then(Value::as_f64),
+    })
+}
@@ -0,0 +1,720 @@
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::auth::fetch_auth_profile;
+use crate::http::{decode_gz_text, normalize_base_url};
+use crate::rooms::{
+    fetch_room_detail, merge_room_objects, normalize_room_name, normalize_shard_for,
+    RoomDetailSnapshot, ScreepsRoomDetailRequest,
+};
+
+const SOCKET_RECONNECT_BASE_DELAY_MS: u64 = 1_000;
+const SOCKET_RECONNECT_MAX_DELAY_MS: u64 = 30_000;
+
+static SOCKET_SESSIONS: OnceLock<Mutex<HashMap<String, mpsc::UnboundedSender<SocketControl>>>> =
+    OnceLock::new();
+static NEXT_SOCKET_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+static ROOM_SUBSCRIPTIONS: OnceLock<Mutex<HashMap<String, RoomSubscription>>> = OnceLock::new();
+static MAP_SUBSCRIPTIONS: OnceLock<Mutex<HashMap<String, HashSet<String>>>> = OnceLock::new();
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsConsoleSubscribeRequest {
+    pub base_url: String,
+    pub token: String,
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsConsoleLineEvent {
+    pub subscription_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard: Option<String>,
+    pub kind: String,
+    pub line: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsSocketErrorEvent {
+    pub subscription_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomSubscription {
+    pub subscription_id: String,
+    pub snapshot: RoomDetailSnapshot,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsRoomUpdateEvent {
+    pub subscription_id: String,
+    pub snapshot: RoomDetailSnapshot,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMapSubscribeRequest {
+    pub base_url: String,
+    pub token: String,
+    pub shard: Option<String>,
+    pub rooms: Vec<String>,
+    pub subscription_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMapSubscription {
+    pub subscription_id: String,
+    pub rooms: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreepsMapUpdateEvent {
+    pub subscription_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard: Option<String>,
+    pub room: String,
+    pub counts: HashMap<String, usize>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub users: HashMap<String, usize>,
+}
+
+#[derive(Debug)]
+struct RoomSubscription {
+    session_id: String,
+    refs: usize,
+    objects: Map<String, Value>,
+    snapshot: RoomDetailSnapshot,
+}
+
+#[derive(Debug)]
+pub(crate) enum SocketControl {
+    Subscribe(Vec<String>),
+    Unsubscribe(Vec<String>),
+    Close,
+}
+
+pub(crate) enum SocketEvent<'a> {
+    Message { channel: &'a str, payload: &'a Value },
+    // Fired after every successful auth, including reconnects, before channels are resubscribed.
+    Authenticated,
+    Error(String),
+    // The session is gone for good (closed, or auth rejected) and will not reconnect.
+    Ended,
+}
+
+pub(crate) type SocketEventHandler = Box<dyn FnMut(&str, SocketEvent<'_>) + Send>;
+
+enum SessionEnd {
+    Closed,
+    AuthFailed,
+    Disconnected(String),
+}
+
+fn socket_sessions() -> &'static Mutex<HashMap<String, mpsc::UnboundedSender<SocketControl>>> {
+    SOCKET_SESSIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn room_subscriptions() -> &'static Mutex<HashMap<String, RoomSubscription>> {
+    ROOM_SUBSCRIPTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn map_subscriptions() -> &'static Mutex<HashMap<String, HashSet<String>>> {
+    MAP_SUBSCRIPTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn socket_url(base_url: &str) -> String {
+    let base_url = normalize_base_url(base_url);
+    let host = if let Some(rest) = base_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        base_url
+    };
+    format!("{}/socket/websocket", host)
+}
+
+fn decode_socket_frame(text: &str) -> Option<(String, Value)> {
+    let decoded = if text.starts_with("gz:") { decode_gz_text(text)? } else { text.to_string() };
+    let Value::Array(mut items) = serde_json::from_str::<Value>(&decoded).ok()? else {
+        return None;
+    };
+    if items.len() < 2 {
+        return None;
+    }
+    let payload = items.swap_remove(1);
+    let channel = items.first()?.as_str()?.to_string();
+    Some((channel, payload))
+}
+
+async fn connect_and_run(
+    url: &str,
+    token: &str,
+    session_id: &str,
+    channels: &mut HashSet<String>,
+    control: &mut mpsc::UnboundedReceiver<SocketControl>,
+    handler: &mut SocketEventHandler,
+    reconnect_delay: &mut Duration,
+) -> SessionEnd {
+    let (stream, _) = match tokio_tungstenite::connect_async(url).await {
+        Ok(connection) => connection,
+        Err(error) => return SessionEnd::Disconnected(format!("socket connect failed: {}", error)),
+    };
+    let (mut sink, mut stream) = stream.split();
+    if let Err(error) = sink.send(Message::Text(format!("auth {}", token).into())).await {
+        return SessionEnd::Disconnected(format!("socket auth send failed: {}", error));
+    }
+    let mut authenticated = false;
+
+    loop {
+        tokio::select! {
+            frame = stream.next() => {
+                let text = match frame {
+                    None => return SessionEnd::Disconnected("socket closed".to_string()),
+                    Some(Err(error)) => {
+                        return SessionEnd::Disconnected(format!("socket error: {}", error));
+                    }
+                    Some(Ok(Message::Close(_))) => {
+                        return SessionEnd::Disconnected("socket closed by server".to_string());
+                    }
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(_)) => continue,
+                };
+
+                if let Some(result) = text.as_str().strip_prefix("auth ") {
+                    if !result.starts_with("ok") {
+                        return SessionEnd::AuthFailed;
+                    }
+                    authenticated = true;
+                    handler(session_id, SocketEvent::Authenticated);
+                    *reconnect_delay = Duration::from_millis(SOCKET_RECONNECT_BASE_DELAY_MS);
+                    for channel in channels.iter() {
+                        let frame = Message::Text(format!("subscribe {}", channel).into());
+                        if let Err(error) = sink.send(frame).await {
+                            return SessionEnd::Disconnected(format!(
+                                "socket subscribe failed: {}",
+                                error
+                            ));
+                        }
+                    }
+                    continue;
+                }
+
+                if let Some((channel, payload)) = decode_socket_frame(text.as_str()) {
+                    handler(session_id, SocketEvent::Message { channel: &channel, payload: &payload });
+                }
+            }
+            command = control.recv() => {
+                let (verb, changed) = match command {
+                    Some(SocketControl::Subscribe(added)) => {
+                        ("subscribe", apply_channel_change(channels, added, true))
+                    }
+                    Some(SocketControl::Unsubscribe(removed)) => {
+                        ("unsubscribe", apply_channel_change(channels, removed, false))
+                    }
+                    Some(SocketControl::Close) | None => {
+                        let _ = sink.send(Message::Close(None)).await;
+                        return SessionEnd::Closed;
+                    }
+                };
+                // Before auth completes the full channel set is subscribed once auth succeeds.
+                if !authenticated {
+                    continue;
+                }
+                for channel in changed {
+                    let frame = Message::Text(format!("{} {}", verb, channel).into());
+                    if let Err(error) = sink.send(frame).await {
+                        return SessionEnd::Disconnected(format!(
+                            "socket {} failed: {}",
+                            verb, error
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn apply_channel_change(
+    channels: &mut HashSet<String>,
+    requested: Vec<String>,
+    subscribe: bool,
+) -> Vec<String> {
+    requested
+        .into_iter()
+        .filter(
+            |channel| {
+                if subscribe {
+                    channels.insert(channel.clone())
+                } else {
+                    channels.remove(channel)
+                }
+            },
+        )
+        .collect()
+}
+
+async fn run_socket_session(
+    session_id: String,
+    base_url: String,
+    token: String,
+    mut channels: HashSet<String>,
+    mut control: mpsc::UnboundedReceiver<SocketControl>,
+    mut handler: SocketEventHandler,
+) {
+    let url = socket_url(&base_url);
+    let mut reconnect_delay = Duration::from_millis(SOCKET_RECONNECT_BASE_DELAY_MS);
+
+    loop {
+        match connect_and_run(
+            &url,
+            &token,
+            &session_id,
+            &mut channels,
+            &mut control,
+            &mut handler,
+            &mut reconnect_delay,
+        )
+        .await
+        {
+            SessionEnd::Closed => break,
+            SessionEnd::AuthFailed => {
+                handler(
+                    &session_id,
+                    SocketEvent::Error("socket authentication failed".to_string()),
+                );
+                break;
+            }
+            SessionEnd::Disconnected(reason) => {
+                handler(&session_id, SocketEvent::Error(reason));
+            }
+        }
+
+        let resume_at = tokio::time::Instant::now() + reconnect_delay;
+        let closed = loop {
+            tokio::select! {
+                _ = tokio::time::sleep_until(resume_at) => break false,
+                command = control.recv() => match command {
+                    Some(SocketControl::Subscribe(added)) => {
+                        apply_channel_change(&mut channels, added, true);
+                    }
+                    Some(SocketControl::Unsubscribe(removed)) => {
+                        apply_channel_change(&mut channels, removed, false);
+                    }
+                    Some(SocketControl::Close) | None => break true,
+                },
+            }
+        };
+        if closed {
+            break;
+        }
+        reconnect_delay =
+            (reconnect_delay * 2).min(Duration::from_millis(SOCKET_RECONNECT_MAX_DELAY_MS));
+    }
+
+    socket_sessions().lock().map(|mut guard| guard.remove(&session_id)).ok();
+    handler(&session_id, SocketEvent::Ended);
+}
+
+pub(crate) fn open_socket_session(
+    base_url: &str,
+    token: &str,
+    channels: Vec<String>,
+    handler: SocketEventHandler,
+) -> Result<String, String> {
+    let session_id = format!("ws-{}", NEXT_SOCKET_SESSION_ID.fetch_add(1, Ordering::Relaxed));
+    let (sender, receiver) = mpsc::unbounded_channel();
+    socket_sessions()
+        .lock()
+        .map_err(|_| "socket session registry is unavailable".to_string())?
+        .insert(session_id.clone(), sender);
+
+    tauri::async_runtime::spawn(run_socket_session(
+        session_id.clone(),
+        base_url.to_string(),
+        token.trim().to_string(),
+        channels.into_iter().collect(),
+        receiver,
+        handler,
+    ));
+    Ok(session_id)
+}
+
+pub(crate) fn send_socket_control(session_id: &str, command: SocketControl) -> bool {
+    let Ok(guard) = socket_sessions().lock() else {
+        return false;
+    };
+    guard.get(session_id).is_some_and(|sender| sender.send(command).is_ok())
+}
+
+pub(crate) fn close_all_socket_sessions() {
+    if let Ok(mut guard) = room_subscriptions().lock() {
+        guard.clear();
+    }
+    if let Ok(mut guard) = map_subscriptions().lock() {
+        guard.clear();
+    }
+    let Ok(mut guard) = socket_sessions().lock() else {
+        return;
+    };
+    for (_, sender) in guard.drain() {
+        let _ = sender.send(SocketControl::Close);
+    }
+}
+
+fn emit_socket_error(app: &AppHandle, session_id: &str, error: String) {
+    let _ = app.emit(
+        "screeps://socket-error",
+        ScreepsSocketErrorEvent { subscription_id: session_id.to_string(), error },
+    );
+}
+
+fn emit_console_lines(app: &AppHandle, session_id: &str, payload: &Value) {
+    let shard = payload.get("shard").and_then(Value::as_str).map(str::to_string);
+    let mut lines = Vec::<(&str, String)>::new();
+    if let Some(messages) = payload.get("messages") {
+        for (key, kind) in [("log", "log"), ("results", "result")] {
+            let Some(items) = messages.get(key).and_then(Value::as_array) else {
+                continue;
+            };
+            for item in items {
+                if let Some(text) = item.as_str() {
+                    lines.push((kind, text.to_string()));
+                }
+            }
+        }
+    }
+    if let Some(error) = payload.get("error").and_then(Value::as_str) {
+        lines.push(("error", error.to_string()));
+    }
+
+    for (kind, line) in lines {
+        let _ = app.emit(
+            "screeps://console-line",
+            ScreepsConsoleLineEvent {
+                subscription_id: session_id.to_string(),
+                shard: shard.clone(),
+                kind: kind.to_string(),
+                line,
+            },
+        );
+    }
+}
+
+#[tauri::command]
+pub async fn screeps_console_subscribe(
+    app: AppHandle,
+    request: ScreepsConsoleSubscribeRequest,
+) -> Result<String, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let user_id = match request.user_id.as_deref().map(str::trim).filter(|value| !value.is_empty())
+    {
+        Some(value) => value.to_string(),
+        None => fetch_auth_profile(&request.base_url, &request.token).await?.self_id,
+    };
+
+    let channel = format!("user:{}/console", user_id);
+    open_socket_session(
+        &request.base_url,
+        &request.token,
+        vec![channel],
+        Box::new(move |session_id, event| match event {
+            SocketEvent::Message { channel, payload } if channel.ends_with("/console") => {
+                emit_console_lines(&app, session_id, payload);
+            }
+            SocketEvent::Error(error) => emit_socket_error(&app, session_id, error),
+            SocketEvent::Message { .. } | SocketEvent::Authenticated | SocketEvent::Ended => {}
+        }),
+    )
+}
+
+#[tauri::command]
+pub fn screeps_console_unsubscribe(subscription_id: String) -> bool {
+    send_socket_control(&subscription_id, SocketControl::Close)
+}
+
+fn merge_object_delta(target: &mut Map<String, Value>, delta: &Map<String, Value>) {
+    for (key, value) in delta {
+        match (target.get_mut(key), value) {
+            (_, Value::Null) => {
+                target.remove(key);
+            }
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                merge_object_delta(existing, nested);
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+// After a reconnect the server resends the full room state, so objects that vanished while
+// disconnected must not linger from the old accumulation.
+fn reset_room_objects(subscription_key: &str, session_id: &str) {
+    let Ok(mut guard) = room_subscriptions().lock() else {
+        return;
+    };
+    if let Some(subscription) =
+        guard.get_mut(subscription_key).filter(|subscription| subscription.session_id == session_id)
+    {
+        subscription.objects.clear();
+    }
+}
+
+fn remove_room_subscription(subscription_key: &str, session_id: &str) {
+    let Ok(mut guard) = room_subscriptions().lock() else {
+        return;
+    };
+    if guard.get(subscription_key).is_some_and(|subscription| subscription.session_id == session_id)
+    {
+        guard.remove(subscription_key);
+    }
+}
+
+fn apply_room_frame(subscription_key: &str, payload: &Value) -> Option<RoomDetailSnapshot> {
+    let mut guard = room_subscriptions().lock().ok()?;
+    let subscription = guard.get_mut(subscription_key)?;
+    if let Some(delta) = payload.get("objects").and_then(Value::as_object) {
+        merge_object_delta(&mut subscription.objects, delta);
+    }
+    let game_time = payload.get("gameTime").and_then(Value::as_f64);
+    subscription.snapshot =
+        merge_room_objects(&subscription.snapshot, &subscription.objects, game_time);
+    Some(subscription.snapshot.clone())
+}
+
+#[tauri::command]
+pub async fn screeps_room_subscribe(
+    app: AppHandle,
+    request: ScreepsRoomDetailRequest,
+) -> Result<ScreepsRoomSubscription, String> {
+    let room_name = normalize_room_name(&request.room_name)?;
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let subscription_key = format!(
+        "{}|{}|{}",
+        normalize_base_url(&request.base_url),
+        shard.as_deref().unwrap_or(""),
+        room_name
+    );
+
+    let existing = |guard: &mut HashMap<String, RoomSubscription>| {
+        guard.get_mut(&subscription_key).map(|subscription| {
+            subscription.refs += 1;
+            ScreepsRoomSubscription {
+                subscription_id: subscription_key.clone(),
+                snapshot: subscription.snapshot.clone(),
+            }
+        })
+    };
+    {
+        let mut guard = room_subscriptions()
+            .lock()
+            .map_err(|_| "room subscription registry is unavailable".to_string())?;
+        if let Some(subscription) = existing(&mut guard) {
+            return Ok(subscription);
+        }
+    }
+
+    let snapshot = fetch_room_detail(&request).await?;
+    let mut guard = room_subscriptions()
+        .lock()
+        .map_err(|_| "room subscription registry is unavailable".to_string())?;
+    if let Some(subscription) = existing(&mut guard) {
+        return Ok(subscription);
+    }
+
+    let channel = match shard.as_deref() {
+        Some(value) => format!("room:{}/{}", value, room_name),
+        None => format!("room:{}", room_name),
+    };
+    let handler_key = subscription_key.clone();
+    let session_id = open_socket_session(
+        &request.base_url,
+        &request.token,
+        vec![channel],
+        Box::new(move |session_id, event| match event {
+            SocketEvent::Message { channel, payload } if channel.starts_with("room:") => {
+                let Some(snapshot) = apply_room_frame(&handler_key, payload) else {
+                    return;
+                };
+                let _ = app.emit(
+                    "screeps://room-update",
+                    ScreepsRoomUpdateEvent { subscription_id: handler_key.clone(), snapshot },
+                );
+            }
+            SocketEvent::Message { .. } => {}
+            SocketEvent::Authenticated => reset_room_objects(&handler_key, session_id),
+            SocketEvent::Error(error) => emit_socket_error(&app, &handler_key, error),
+            SocketEvent::Ended => remove_room_subscription(&handler_key, session_id),
+        }),
+    )?;
+    guard.insert(
+        subscription_key.clone(),
+        RoomSubscription { session_id, refs: 1, objects: Map::new(), snapshot: snapshot.clone() },
+    );
+
+    Ok(ScreepsRoomSubscription { subscription_id: subscription_key, snapshot })
+}
+
+#[tauri::command]
+pub fn screeps_room_unsubscribe(subscription_id: String) -> Result<bool, String> {
+    let mut guard = room_subscriptions()
+        .lock()
+        .map_err(|_| "room subscription registry is unavailable".to_string())?;
+    let Some(subscription) = guard.get_mut(&subscription_id) else {
+        return Ok(false);
+    };
+    subscription.refs = subscription.refs.saturating_sub(1);
+    if subscription.refs == 0 {
+        if let Some(subscription) = guard.remove(&subscription_id) {
+            send_socket_control(&subscription.session_id, SocketControl::Close);
+        }
+    }
+    Ok(true)
+}
+
+fn map_object_kind(key: &str) -> Option<&'static str> {
+    match key {
+        "w" => Some("constructedWall"),
+        "r" => Some("road"),
+        "pb" => Some("powerBank"),
+        "p" => Some("portal"),
+        "s" => Some("source"),
+        "m" => Some("mineral"),
+        "c" => Some("controller"),
+        "k" => Some("keeperLair"),
+        "d" => Some("deposit"),
+        _ => None,
+    }
+}
+
+fn map_update_event(
+    subscription_id: &str,
+    channel: &str,
+    payload: &Value,
+) -> Option<ScreepsMapUpdateEvent> {
+    let target = channel.strip_prefix("roomMap2:")?;
+    let (shard, room) = match target.rsplit_once('/') {
+        Some((shard, room)) => (Some(shard.to_string()), room.to_string()),
+        None => (None, target.to_string()),
+    };
+    let mut counts = HashMap::new();
+    let mut users = HashMap::new();
+    for (key, positions) in payload.as_object()? {
+        let count = positions.as_array().map_or(0, Vec::len);
+        if count == 0 {
+            continue;
+        }
+        // Anything that is not a known object key is a user id listing that user's objects.
+        match map_object_kind(key) {
+            Some(kind) => *counts.entry(kind.to_string()).or_insert(0) += count,
+            None => *users.entry(key.clone()).or_insert(0) += count,
+        }
+    }
+    Some(ScreepsMapUpdateEvent {
+        subscription_id: subscription_id.to_string(),
+        shard,
+        room,
+        counts,
+        users,
+    })
+}
+
+#[tauri::command]
+pub fn screeps_map_subscribe(
+    app: AppHandle,
+    request: ScreepsMapSubscribeRequest,
+) -> Result<ScreepsMapSubscription, String> {
+    if request.token.trim().is_empty() {
+        return Err("Token cannot be empty".to_string());
+    }
+    let shard = normalize_shard_for(&request.base_url, request.shard.as_deref());
+    let mut rooms = Vec::new();
+    for room in &request.rooms {
+        let room = normalize_room_name(room)?;
+        if !rooms.contains(&room) {
+            rooms.push(room);
+        }
+    }
+    let channels = rooms
+        .iter()
+        .map(|room| match shard.as_deref() {
+            Some(value) => format!("roomMap2:{}/{}", value, room),
+            None => format!("roomMap2:{}", room),
+        })
+        .collect::<HashSet<String>>();
+
+    let mut guard = map_subscriptions()
+        .lock()
+        .map_err(|_| "map subscription registry is unavailable".to_string())?;
+
+    // Panning reuses the open session and only sends the difference between the two room sets.
+    if let Some(subscription_id) = request.subscription_id.as_deref() {
+        if let Some(current) = guard.get(subscription_id) {
+            let added = channels.difference(current).cloned().collect::<Vec<String>>();
+            let removed = current.difference(&channels).cloned().collect::<Vec<String>>();
+            let delivered = (removed.is_empty()
+                || send_socket_control(subscription_id, SocketControl::Unsubscribe(removed)))
+                && (added.is_empty()
+                    || send_socket_control(subscription_id, SocketControl::Subscribe(added)));
+            if delivered {
+                guard.insert(subscription_id.to_string(), channels);
+                return Ok(ScreepsMapSubscription {
+                    subscription_id: subscription_id.to_string(),
+                    rooms,
+                });
+            }
+            // The session ended on its own (e.g. auth failure), so start a fresh one below.
+            guard.remove(subscription_id);
+        }
+    }
+
+    let session_id = open_socket_session(
+        &request.base_url,
+        &request.token,
+        channels.iter().cloned().collect(),
+        Box::new(move |session_id, event| match event {
+            SocketEvent::Message { channel, payload } => {
+                if let Some(update) = map_update_event(session_id, channel, payload) {
+                    let _ = app.emit("screeps://map-update", update);
+                }
+            }
+            SocketEvent::Authenticated => {}
+            SocketEvent::Error(error) => emit_socket_error(&app, session_id, error),
+            SocketEvent::Ended => {
+                map_subscriptions().lock().map(|mut guard| guard.remove(session_id)).ok();
+            }
+        }),
+    )?;
+    guard.insert(session_id.clone(), channels);
+    Ok(ScreepsMapSubscription { subscription_id: session_id, rooms })
+}
+
+#[tauri::command]
+pub fn screeps_map_unsubscribe(subscription_id: String) -> Result<bool, String> {
+    let mut guard = map_subscriptions()
+        .lock()
+        .map_err(|_| "map subscription registry is unavailable".to_string())?;
+    if guard.remove(&subscription_id).is_none() {
+        return Ok(false);
+    }
+    Ok(send_socket_control(&subscription_id, SocketControl::Close))
+}